@@ -0,0 +1,51 @@
+use calibration::{calibration_value, DigitScanner};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// The scan part 2 used before the automaton: every word is compared at
+/// every byte offset.
+fn match_spelled_out(s: &str) -> Option<u32> {
+    let words = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    words
+        .iter()
+        .position(|w| s.starts_with(w))
+        .map(|j| j as u32 + 1)
+}
+
+fn naive(input: &str) -> u32 {
+    input
+        .lines()
+        .map(|line| {
+            let mut it = (0..line.len()).filter_map(|i| {
+                let c = line[i..].chars().next().unwrap();
+                c.to_digit(10).or_else(|| match_spelled_out(&line[i..]))
+            });
+            let first = it.next().unwrap();
+            let last = it.next_back().unwrap_or(first);
+            first * 10 + last
+        })
+        .sum()
+}
+
+fn automaton(input: &str, scanner: &DigitScanner) -> u32 {
+    input
+        .lines()
+        .map(|line| calibration_value(line, scanner).unwrap())
+        .sum()
+}
+
+fn bench(c: &mut Criterion) {
+    let input = include_str!("../../part_2/input.txt");
+    let scanner = DigitScanner::english();
+    assert_eq!(naive(input), automaton(input, &scanner));
+    let mut group = c.benchmark_group("part_2");
+    group.bench_function("naive", |b| b.iter(|| naive(black_box(input))));
+    group.bench_function("automaton", |b| {
+        b.iter(|| automaton(black_box(input), &scanner))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
@@ -0,0 +1,140 @@
+mod report;
+mod scanner;
+mod stream;
+mod vocabulary;
+
+pub use report::{line_reports, render_csv, render_table, LineReport, Pick, Source};
+pub use scanner::{DigitScanner, Match};
+pub use stream::{sum_file_parallel, sum_reader, StreamError, MAX_LINE};
+pub use vocabulary::{parse_vocabulary, Language, Vocabulary, VocabularyError};
+
+use std::fmt;
+use std::str::FromStr;
+
+/// The two-digit number formed by the first and last digit tokens of a line.
+pub fn calibration_value(line: &str, scanner: &DigitScanner) -> Option<u32> {
+    let (first, last) = scanner.first_and_last(line)?;
+    Some(first.value * 10 + last.value)
+}
+
+/// What to do with a line that has no digit tokens, blank lines included.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MissingDigits {
+    Skip,
+    Zero,
+    #[default]
+    Error,
+}
+
+impl FromStr for MissingDigits {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<MissingDigits, String> {
+        match s {
+            "skip" => Ok(MissingDigits::Skip),
+            "zero" => Ok(MissingDigits::Zero),
+            "error" => Ok(MissingDigits::Error),
+            other => Err(format!(
+                "unknown policy {other:?}, expected skip, zero or error"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct NoDigits {
+    /// One-based line number.
+    pub line: usize,
+    pub text: String,
+}
+
+impl fmt::Display for NoDigits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: no digits in {:?}", self.line, self.text)
+    }
+}
+
+impl std::error::Error for NoDigits {}
+
+/// The calibration value of each line as `(line number, value)`, with
+/// digit-less lines handled by `policy`. Skipped lines are left out.
+pub fn calibration_values(
+    input: &str,
+    scanner: &DigitScanner,
+    policy: MissingDigits,
+) -> Result<Vec<(usize, u32)>, NoDigits> {
+    let mut values = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        match (calibration_value(line, scanner), policy) {
+            (Some(value), _) => values.push((i + 1, value)),
+            (None, MissingDigits::Skip) => {}
+            (None, MissingDigits::Zero) => values.push((i + 1, 0)),
+            (None, MissingDigits::Error) => {
+                return Err(NoDigits {
+                    line: i + 1,
+                    text: line.to_string(),
+                })
+            }
+        }
+    }
+    Ok(values)
+}
+
+pub fn calibration_sum(
+    input: &str,
+    scanner: &DigitScanner,
+    policy: MissingDigits,
+) -> Result<u64, NoDigits> {
+    let values = calibration_values(input, scanner, policy)?;
+    Ok(values.iter().map(|&(_, value)| u64::from(value)).sum())
+}
+
+#[test]
+fn example_1() {
+    let s = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+    let values: Vec<Option<u32>> = s
+        .lines()
+        .map(|l| calibration_value(l, &DigitScanner::digits()))
+        .collect();
+    assert_eq!(values, [Some(12), Some(38), Some(15), Some(77)]);
+}
+
+#[test]
+fn example_2() {
+    let s = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+    let scanner = DigitScanner::english();
+    let total: u32 = s
+        .lines()
+        .map(|l| calibration_value(l, &scanner).unwrap())
+        .sum();
+    assert_eq!(total, 281);
+}
+
+#[test]
+fn lines_without_digits() {
+    let s = "a1b\n\nnone here\n7x8";
+    let scanner = DigitScanner::digits();
+    let values = |policy| calibration_values(s, &scanner, policy);
+    assert_eq!(values(MissingDigits::Skip), Ok(vec![(1, 11), (4, 78)]));
+    assert_eq!(
+        values(MissingDigits::Zero),
+        Ok(vec![(1, 11), (2, 0), (3, 0), (4, 78)])
+    );
+    assert_eq!(
+        values(MissingDigits::Error),
+        Err(NoDigits {
+            line: 2,
+            text: String::new()
+        })
+    );
+    assert_eq!(calibration_sum(s, &scanner, MissingDigits::Skip), Ok(89));
+}
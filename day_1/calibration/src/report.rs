@@ -0,0 +1,244 @@
+use crate::{DigitScanner, Match};
+
+/// Whether a token was written as digits or spelled out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Digit,
+    Word,
+}
+
+/// A token picked as the first or last digit of a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pick {
+    pub token: String,
+    pub at: Match,
+    pub source: Source,
+}
+
+impl Pick {
+    fn new(line: &str, at: Match) -> Pick {
+        let token = &line[at.start..at.end];
+        let source = if token.bytes().all(|b| b.is_ascii_digit()) {
+            Source::Digit
+        } else {
+            Source::Word
+        };
+        Pick {
+            token: token.to_string(),
+            at,
+            source,
+        }
+    }
+}
+
+/// How one line reads under the part 1 and part 2 scanners. The picks are
+/// the part 2 ones, since only they can be words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineReport {
+    /// One-based line number.
+    pub line: usize,
+    pub part_1: Option<u32>,
+    pub part_2: Option<u32>,
+    pub picks: Option<(Pick, Pick)>,
+}
+
+impl LineReport {
+    pub fn new(line: usize, text: &str, digits: &DigitScanner, words: &DigitScanner) -> Self {
+        let picks = words
+            .first_and_last(text)
+            .map(|(first, last)| (Pick::new(text, first), Pick::new(text, last)));
+        Self {
+            line,
+            part_1: crate::calibration_value(text, digits),
+            part_2: picks
+                .as_ref()
+                .map(|(first, last)| first.at.value * 10 + last.at.value),
+            picks,
+        }
+    }
+
+    pub fn disagrees(&self) -> bool {
+        self.part_1 != self.part_2
+    }
+
+    fn cells(&self) -> [String; 10] {
+        let value = |v: Option<u32>| v.map_or(String::new(), |v| v.to_string());
+        let pick = |p: Option<&Pick>| match p {
+            Some(p) => [
+                p.token.clone(),
+                p.at.start.to_string(),
+                match p.source {
+                    Source::Digit => "digit".to_string(),
+                    Source::Word => "word".to_string(),
+                },
+            ],
+            None => Default::default(),
+        };
+        let [first, first_at, first_source] = pick(self.picks.as_ref().map(|(f, _)| f));
+        let [last, last_at, last_source] = pick(self.picks.as_ref().map(|(_, l)| l));
+        [
+            self.line.to_string(),
+            first,
+            first_at,
+            first_source,
+            last,
+            last_at,
+            last_source,
+            value(self.part_1),
+            value(self.part_2),
+            if self.disagrees() { "*" } else { "" }.to_string(),
+        ]
+    }
+}
+
+const HEADERS: [&str; 10] = [
+    "line",
+    "first",
+    "first_at",
+    "first_from",
+    "last",
+    "last_at",
+    "last_from",
+    "part_1",
+    "part_2",
+    "differs",
+];
+
+pub fn line_reports(input: &str, digits: &DigitScanner, words: &DigitScanner) -> Vec<LineReport> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, text)| LineReport::new(i + 1, text, digits, words))
+        .collect()
+}
+
+/// Text table, one row per line, with each pick as `token@byte`. Lines
+/// where the two parts disagree are marked with a leading `*`; the CSV has
+/// every field.
+pub fn render_table(reports: &[LineReport]) -> String {
+    let pick = |p: &Pick| format!("{}@{}", p.token, p.at.start);
+    let picks: Vec<(String, String)> = reports
+        .iter()
+        .map(|r| match &r.picks {
+            Some((first, last)) => (pick(first), pick(last)),
+            None => Default::default(),
+        })
+        .collect();
+    let line_w = reports
+        .last()
+        .map_or(0, |r| r.line.to_string().len())
+        .max(4);
+    let first_w = picks
+        .iter()
+        .map(|(f, _)| f.chars().count())
+        .fold(5, usize::max);
+    let last_w = picks
+        .iter()
+        .map(|(_, l)| l.chars().count())
+        .fold(4, usize::max);
+    let value = |v: Option<u32>| v.map_or(String::new(), |v| v.to_string());
+    let part_w = reports
+        .iter()
+        .flat_map(|r| [r.part_1, r.part_2])
+        .map(|v| value(v).len())
+        .fold(6, usize::max);
+
+    let mut out = format!(
+        "  {:>line_w$}  {:<first_w$}  {:<last_w$}  {:>part_w$}  {:>part_w$}\n",
+        "line", "first", "last", "part_1", "part_2"
+    );
+    for (report, (first, last)) in reports.iter().zip(&picks) {
+        let row = format!(
+            "{} {:>line_w$}  {first:<first_w$}  {last:<last_w$}  {:>part_w$}  {:>part_w$}",
+            if report.disagrees() { '*' } else { ' ' },
+            report.line,
+            value(report.part_1),
+            value(report.part_2),
+        );
+        out += row.trim_end();
+        out += "\n";
+    }
+    out
+}
+
+/// Quotes a field holding a comma, quote or line break, as tokens from a
+/// custom vocabulary may.
+fn csv_field(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+pub fn render_csv(reports: &[LineReport]) -> String {
+    let mut out = HEADERS.join(",") + "\n";
+    for report in reports {
+        let cells: Vec<String> = report.cells().iter().map(|c| csv_field(c)).collect();
+        out += &cells.join(",");
+        out += "\n";
+    }
+    out
+}
+
+#[cfg(test)]
+#[test]
+fn table_marks_disagreements() {
+    let s = "two1nine\n1abc2\n7pqrstsixteen\nnothing";
+    let reports = line_reports(s, &DigitScanner::digits(), &DigitScanner::english());
+    let table = render_table(&reports);
+    assert_eq!(
+        table.lines().collect::<Vec<_>>(),
+        [
+            "  line  first  last    part_1  part_2",
+            "*    1  two@0  nine@4      11      29",
+            "     2  1@0    2@4         12      12",
+            "*    3  7@0    six@6       77      76",
+            "     4",
+        ]
+    );
+}
+
+#[test]
+fn csv_has_every_field() {
+    let reports = line_reports(
+        "abcone2threexyz\nnothing",
+        &DigitScanner::digits(),
+        &DigitScanner::english(),
+    );
+    let csv = render_csv(&reports);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "line,first,first_at,first_from,last,last_at,last_from,part_1,part_2,differs",
+            "1,one,3,word,three,7,word,22,13,*",
+            "2,,,,,,,,,",
+        ]
+    );
+}
+
+#[test]
+fn csv_quotes_awkward_tokens() {
+    let words = crate::Vocabulary::digits()
+        .with_token("a,b", 4)
+        .with_token("\"q\"", 7)
+        .scanner();
+    let reports = line_reports("xa,by\"q\"", &DigitScanner::digits(), &words);
+    assert_eq!(
+        render_csv(&reports).lines().nth(1),
+        Some(r#"1,"a,b",1,word,"""q""",5,word,,47,*"#)
+    );
+}
+
+#[test]
+fn agreeing_lines_are_not_flagged() {
+    let reports = line_reports(
+        "a1b2c3\ntreb7uchet",
+        &DigitScanner::digits(),
+        &DigitScanner::english(),
+    );
+    assert!(reports.iter().all(|r| !r.disagrees()));
+    let (first, last) = reports[1].picks.clone().unwrap();
+    assert_eq!((first.source, last.at.start), (Source::Digit, 4));
+}
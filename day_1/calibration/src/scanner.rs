@@ -0,0 +1,270 @@
+use crate::vocabulary::Vocabulary;
+#[cfg(test)]
+use crate::vocabulary::{DIGITS, ENGLISH};
+
+/// A digit token found in a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    /// Byte offset of the token's first byte.
+    pub start: usize,
+    /// Byte offset just past the token's last byte.
+    pub end: usize,
+    pub value: u32,
+}
+
+const NO_STATE: u32 = u32::MAX;
+
+/// A dense Aho–Corasick automaton over byte strings.
+#[derive(Debug, Clone)]
+struct Automaton {
+    /// Complete transition table: one row of 256 next states per state.
+    delta: Vec<[u32; 256]>,
+    /// Tokens ending at each state, as `(length, value)`, shortest first.
+    outputs: Vec<Vec<(usize, u32)>>,
+}
+
+impl Automaton {
+    /// With `fold_case`, tokens are lowercased and ASCII capitals step like
+    /// their lowercase letters.
+    fn new<'a>(tokens: impl IntoIterator<Item = (&'a [u8], u32)>, fold_case: bool) -> Automaton {
+        let mut delta = vec![[NO_STATE; 256]];
+        let mut outputs: Vec<Vec<(usize, u32)>> = vec![Vec::new()];
+        for (token, value) in tokens {
+            let mut state = 0;
+            for &b in token {
+                let b = if fold_case { b.to_ascii_lowercase() } else { b };
+                if delta[state][b as usize] == NO_STATE {
+                    delta[state][b as usize] = delta.len() as u32;
+                    delta.push([NO_STATE; 256]);
+                    outputs.push(Vec::new());
+                }
+                state = delta[state][b as usize] as usize;
+            }
+            if !token.is_empty() {
+                outputs[state].push((token.len(), value));
+            }
+        }
+
+        // Breadth-first, so each state's failure target is finished first.
+        let mut fail = vec![0usize; delta.len()];
+        let mut queue = std::collections::VecDeque::new();
+        for next in delta[0].iter_mut() {
+            match *next {
+                NO_STATE => *next = 0,
+                child => queue.push_back(child as usize),
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            let mut inherited = outputs[fail[state]].clone();
+            outputs[state].append(&mut inherited);
+            outputs[state].sort_unstable();
+            let fallback = delta[fail[state]];
+            for (next, fallback) in delta[state].iter_mut().zip(fallback) {
+                match *next {
+                    NO_STATE => *next = fallback,
+                    child => {
+                        fail[child as usize] = fallback as usize;
+                        queue.push_back(child as usize);
+                    }
+                }
+            }
+        }
+        if fold_case {
+            for row in delta.iter_mut() {
+                for upper in b'A'..=b'Z' {
+                    row[upper as usize] = row[upper.to_ascii_lowercase() as usize];
+                }
+            }
+        }
+        Automaton { delta, outputs }
+    }
+
+    fn step(&self, state: usize, b: u8) -> usize {
+        self.delta[state][b as usize] as usize
+    }
+}
+
+/// Scans lines for digit tokens. Every token occurrence is reported,
+/// including overlapping ones such as both words in "eightwo".
+///
+/// Alongside the forward automaton sits one over the reversed tokens, so the
+/// last token can be found by reading only the tail of a line.
+#[derive(Debug, Clone)]
+pub struct DigitScanner {
+    forward: Automaton,
+    backward: Automaton,
+    longest: usize,
+}
+
+impl DigitScanner {
+    pub fn new<'a>(tokens: impl IntoIterator<Item = (&'a str, u32)>) -> DigitScanner {
+        DigitScanner::build(tokens, false)
+    }
+
+    pub(crate) fn build<'a>(
+        tokens: impl IntoIterator<Item = (&'a str, u32)>,
+        fold_case: bool,
+    ) -> DigitScanner {
+        let tokens: Vec<(&[u8], u32)> = tokens
+            .into_iter()
+            .map(|(token, value)| (token.as_bytes(), value))
+            .collect();
+        let reversed: Vec<(Vec<u8>, u32)> = tokens
+            .iter()
+            .map(|&(token, value)| (token.iter().rev().copied().collect(), value))
+            .collect();
+        DigitScanner {
+            forward: Automaton::new(tokens.iter().copied(), fold_case),
+            backward: Automaton::new(reversed.iter().map(|(t, v)| (t.as_slice(), *v)), fold_case),
+            longest: tokens.iter().map(|(t, _)| t.len()).max().unwrap_or(0),
+        }
+    }
+
+    /// The ten ASCII digits.
+    pub fn digits() -> DigitScanner {
+        Vocabulary::digits().scanner()
+    }
+
+    /// ASCII digits plus the English words "one" to "nine".
+    pub fn english() -> DigitScanner {
+        Vocabulary::english().scanner()
+    }
+
+    /// Every token in `line`, in order of where they end.
+    pub fn find_all<'a>(&'a self, line: &'a str) -> impl Iterator<Item = Match> + 'a {
+        let mut state = 0;
+        line.bytes().enumerate().flat_map(move |(i, b)| {
+            state = self.forward.step(state, b);
+            self.forward.outputs[state]
+                .iter()
+                .map(move |&(len, value)| Match {
+                    start: i + 1 - len,
+                    end: i + 1,
+                    value,
+                })
+        })
+    }
+
+    /// The token starting earliest in `line`. Of tokens starting at the same
+    /// place, the shortest wins.
+    pub fn first(&self, line: &str) -> Option<Match> {
+        let mut state = 0;
+        let mut best: Option<Match> = None;
+        for (i, &b) in line.as_bytes().iter().enumerate() {
+            // Nothing ending from here on can start before the best so far.
+            if best.is_some_and(|m| i + 1 >= m.start + self.longest) {
+                break;
+            }
+            state = self.forward.step(state, b);
+            for &(len, value) in &self.forward.outputs[state] {
+                let start = i + 1 - len;
+                if best.is_none_or(|m| start < m.start) {
+                    best = Some(Match {
+                        start,
+                        end: i + 1,
+                        value,
+                    });
+                }
+            }
+        }
+        best
+    }
+
+    /// The token starting latest in `line`, read from the end. Of tokens
+    /// starting at the same place, the shortest wins.
+    pub fn last(&self, line: &str) -> Option<Match> {
+        let mut state = 0;
+        for (start, &b) in line.as_bytes().iter().enumerate().rev() {
+            state = self.backward.step(state, b);
+            if let Some(&(len, value)) = self.backward.outputs[state].first() {
+                return Some(Match {
+                    start,
+                    end: start + len,
+                    value,
+                });
+            }
+        }
+        None
+    }
+
+    /// The tokens starting earliest and latest in `line`.
+    pub fn first_and_last(&self, line: &str) -> Option<(Match, Match)> {
+        Some((self.first(line)?, self.last(line)?))
+    }
+}
+
+#[test]
+fn overlapping_words() {
+    let scanner = DigitScanner::english();
+    let values = |line| -> Vec<u32> { scanner.find_all(line).map(|m| m.value).collect() };
+    assert_eq!(values("eightwo"), [8, 2]);
+    assert_eq!(values("twone"), [2, 1]);
+    assert_eq!(values("oneight"), [1, 8]);
+    assert_eq!(values("sevenine3"), [7, 9, 3]);
+    assert_eq!(values("nothing"), []);
+}
+
+#[test]
+fn first_and_last_by_start() {
+    let scanner = DigitScanner::english();
+    let (first, last) = scanner.first_and_last("zoneight234").unwrap();
+    assert_eq!(
+        first,
+        Match {
+            start: 1,
+            end: 4,
+            value: 1
+        }
+    );
+    assert_eq!(
+        last,
+        Match {
+            start: 10,
+            end: 11,
+            value: 4
+        }
+    );
+    assert_eq!(scanner.first_and_last("xyz"), None);
+}
+
+#[test]
+fn longer_token_can_start_before_an_earlier_ending_one() {
+    let scanner = DigitScanner::new([("ab", 1), ("xabc", 2)]);
+    let (first, last) = scanner.first_and_last("xabc").unwrap();
+    assert_eq!((first.value, last.value), (2, 1));
+}
+
+#[test]
+fn matches_naive_scan() {
+    let scanner = DigitScanner::english();
+    let line = "4nineeightseven2zoneight234xtwone3fourabcone2threexyz";
+    let naive: Vec<(usize, u32)> = (0..line.len())
+        .flat_map(|i| {
+            DIGITS
+                .iter()
+                .zip(0..)
+                .chain(ENGLISH.iter().zip(1..))
+                .filter(move |(t, _)| line[i..].starts_with(**t))
+                .map(move |(_, v)| (i, v))
+        })
+        .collect();
+    let mut found: Vec<(usize, u32)> = scanner.find_all(line).map(|m| (m.start, m.value)).collect();
+    found.sort_unstable();
+    assert_eq!(found, naive);
+}
+
+#[test]
+fn first_and_last_agree_with_find_all() {
+    let scanner = DigitScanner::english();
+    let text = "4nineeightseven2zoneight234xtwone3fourabcone2threexyz";
+    for i in 0..text.len() {
+        for j in i..=text.len() {
+            let line = &text[i..j];
+            let all: Vec<Match> = scanner.find_all(line).collect();
+            let first = all.iter().min_by_key(|m| (m.start, m.end)).copied();
+            let last = all.iter().max_by_key(|m| (m.start, !m.end)).copied();
+            assert_eq!(scanner.first(line), first, "{line}");
+            assert_eq!(scanner.last(line), last, "{line}");
+        }
+    }
+}
@@ -0,0 +1,311 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::{calibration_value, DigitScanner, MissingDigits, NoDigits};
+
+/// The longest line, in bytes without its line ending, that streaming will
+/// hold in memory.
+pub const MAX_LINE: usize = 1 << 20;
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    NoDigits(NoDigits),
+    /// A line longer than [`MAX_LINE`] bytes.
+    LineTooLong {
+        line: usize,
+    },
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "{e}"),
+            StreamError::NoDigits(e) => write!(f, "{e}"),
+            StreamError::LineTooLong { line } => {
+                write!(f, "line {line}: longer than {MAX_LINE} bytes")
+            }
+        }
+    }
+}
+
+impl std::error::Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+    fn from(e: io::Error) -> StreamError {
+        StreamError::Io(e)
+    }
+}
+
+impl From<NoDigits> for StreamError {
+    fn from(e: NoDigits) -> StreamError {
+        StreamError::NoDigits(e)
+    }
+}
+
+/// A sum and line count, or the first error.
+type Counted = Result<(u64, usize), StreamError>;
+
+/// The calibration sum of a stream, read one line at a time. Lines over
+/// [`MAX_LINE`] bytes are an error.
+pub fn sum_reader(
+    reader: impl BufRead,
+    scanner: &DigitScanner,
+    policy: MissingDigits,
+) -> Result<u64, StreamError> {
+    sum_lines(reader, scanner, policy).map(|(sum, _)| sum)
+}
+
+/// Reads the next line into `buf` without its line ending, or returns
+/// `false` at the end of the stream. `line` numbers the error if the line is
+/// over [`MAX_LINE`] bytes.
+fn read_line(
+    reader: &mut impl BufRead,
+    buf: &mut Vec<u8>,
+    line: usize,
+) -> Result<bool, StreamError> {
+    buf.clear();
+    let mut read = false;
+    loop {
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            return Ok(read);
+        }
+        read = true;
+        let newline = available.iter().position(|&b| b == b'\n');
+        let end = newline.unwrap_or(available.len());
+        if buf.len() + end > MAX_LINE {
+            return Err(StreamError::LineTooLong { line });
+        }
+        buf.extend_from_slice(&available[..end]);
+        reader.consume(newline.map_or(end, |i| i + 1));
+        if newline.is_some() {
+            return Ok(true);
+        }
+    }
+}
+
+/// The sum and line count of a stream. Line numbers in errors count from
+/// the start of the stream.
+fn sum_lines(mut reader: impl BufRead, scanner: &DigitScanner, policy: MissingDigits) -> Counted {
+    let mut sum = 0;
+    let mut lines = 0;
+    let mut buf = Vec::new();
+    while read_line(&mut reader, &mut buf, lines + 1)? {
+        lines += 1;
+        let line =
+            std::str::from_utf8(&buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match (calibration_value(line, scanner), policy) {
+            (Some(value), _) => sum += u64::from(value),
+            (None, MissingDigits::Skip | MissingDigits::Zero) => {}
+            (None, MissingDigits::Error) => {
+                return Err(NoDigits {
+                    line: lines,
+                    text: line.to_string(),
+                }
+                .into())
+            }
+        }
+    }
+    Ok((sum, lines))
+}
+
+/// The number of bytes up to and including the next newline, or to the end
+/// of the stream, skipped without holding on to them.
+fn skip_line(reader: &mut impl BufRead) -> io::Result<u64> {
+    let mut skipped = 0;
+    loop {
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            return Ok(skipped);
+        }
+        match available.iter().position(|&b| b == b'\n') {
+            Some(i) => {
+                reader.consume(i + 1);
+                return Ok(skipped + i as u64 + 1);
+            }
+            None => {
+                let n = available.len();
+                reader.consume(n);
+                skipped += n as u64;
+            }
+        }
+    }
+}
+
+/// Byte offsets splitting the file into about `chunks` pieces, each ending
+/// just after a newline or at the end of the file.
+fn chunk_boundaries(file: &mut File, len: u64, chunks: usize) -> io::Result<Vec<u64>> {
+    let mut boundaries = vec![0];
+    for k in 1..chunks as u64 {
+        let target = (len * k / chunks as u64).max(*boundaries.last().unwrap());
+        if target == 0 || target >= len {
+            continue;
+        }
+        // Read from the byte before, so a target just after a newline stays.
+        file.seek(SeekFrom::Start(target - 1))?;
+        let boundary = target - 1 + skip_line(&mut BufReader::new(&mut *file))?;
+        if boundary >= len {
+            // No newline after this target, so none after later ones either.
+            break;
+        }
+        if boundary > *boundaries.last().unwrap() {
+            boundaries.push(boundary);
+        }
+    }
+    boundaries.push(len);
+    Ok(boundaries)
+}
+
+fn sum_chunk(
+    path: &Path,
+    range: (u64, u64),
+    scanner: &DigitScanner,
+    policy: MissingDigits,
+) -> Counted {
+    let (start, end) = range;
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(start))?;
+    sum_lines(BufReader::new(file.take(end - start)), scanner, policy)
+}
+
+/// The calibration sum of a file, split into line-aligned chunks. The
+/// chunks are shared among at most one thread per available core, and each
+/// thread holds one line of up to [`MAX_LINE`] bytes at a time.
+pub fn sum_file_parallel(
+    path: &Path,
+    scanner: &DigitScanner,
+    policy: MissingDigits,
+    chunks: usize,
+) -> Result<u64, StreamError> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let boundaries = chunk_boundaries(&mut file, len, chunks.max(1))?;
+    let ranges: Vec<(u64, u64)> = boundaries.windows(2).map(|w| (w[0], w[1])).collect();
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let workers = ranges.len().min(cores).max(1);
+    // Worker `w` reads chunks w, w + workers, w + 2 * workers and so on.
+    let mut results: Vec<(usize, Counted)> = std::thread::scope(|s| {
+        let ranges = &ranges;
+        let handles: Vec<_> = (0..workers)
+            .map(|w| {
+                s.spawn(move || {
+                    (w..ranges.len())
+                        .step_by(workers)
+                        .map(|i| (i, sum_chunk(path, ranges[i], scanner, policy)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().expect("chunk thread panicked"))
+            .collect()
+    });
+    results.sort_by_key(|&(i, _)| i);
+
+    let mut sum = 0;
+    let mut lines_before = 0;
+    for (_, result) in results {
+        match result {
+            Ok((chunk_sum, lines)) => {
+                sum += chunk_sum;
+                lines_before += lines;
+            }
+            Err(StreamError::NoDigits(mut e)) => {
+                e.line += lines_before;
+                return Err(e.into());
+            }
+            Err(StreamError::LineTooLong { line }) => {
+                return Err(StreamError::LineTooLong {
+                    line: line + lines_before,
+                })
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(sum)
+}
+
+#[cfg(test)]
+fn temp_file(name: &str, contents: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("calibration-{}-{name}", std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn reader_matches_in_memory_sum() {
+    let s = "two1nine\r\neightwothree\nabcone2threexyz\n\nxtwone3four\n";
+    let scanner = DigitScanner::english();
+    let policy = MissingDigits::Skip;
+    assert_eq!(
+        sum_reader(s.as_bytes(), &scanner, policy).unwrap(),
+        crate::calibration_sum(s, &scanner, policy).unwrap()
+    );
+    match sum_reader(s.as_bytes(), &scanner, MissingDigits::Error) {
+        Err(StreamError::NoDigits(e)) => assert_eq!(e.line, 4),
+        other => panic!("expected a missing digit error, got {other:?}"),
+    }
+}
+
+#[test]
+fn chunks_agree_for_any_count() {
+    let lines: Vec<String> = (0..200)
+        .map(|i| format!("x{}y{}z", i % 10, (i * 7) % 10))
+        .collect();
+    let text = lines.join("\n");
+    let path = temp_file("chunks", &text);
+    let scanner = DigitScanner::digits();
+    let expected = sum_reader(text.as_bytes(), &scanner, MissingDigits::Error).unwrap();
+    for chunks in [1, 2, 3, 7, 64, 1000, 5000] {
+        let sum = sum_file_parallel(&path, &scanner, MissingDigits::Error, chunks).unwrap();
+        assert_eq!(sum, expected, "{chunks} chunks");
+    }
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn parallel_errors_report_global_line_numbers() {
+    let mut text = "1\n".repeat(500);
+    text.push_str("nothing\n");
+    text.push_str(&"2\n".repeat(500));
+    let path = temp_file("errors", &text);
+    let scanner = DigitScanner::digits();
+    for chunks in [1, 4, 16] {
+        match sum_file_parallel(&path, &scanner, MissingDigits::Error, chunks) {
+            Err(StreamError::NoDigits(e)) => assert_eq!(e.line, 501, "{chunks} chunks"),
+            other => panic!("expected a missing digit error, got {other:?}"),
+        }
+    }
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn long_lines_are_rejected() {
+    let mut text = "1\n".repeat(300);
+    text.push_str(&"x".repeat(MAX_LINE + 1));
+    text.push_str("\n2\n");
+    let scanner = DigitScanner::digits();
+    let policy = MissingDigits::Skip;
+    match sum_reader(text.as_bytes(), &scanner, policy) {
+        Err(StreamError::LineTooLong { line }) => assert_eq!(line, 301),
+        other => panic!("expected a long line error, got {other:?}"),
+    }
+    let path = temp_file("long", &text);
+    for chunks in [1, 4] {
+        match sum_file_parallel(&path, &scanner, policy, chunks) {
+            Err(StreamError::LineTooLong { line }) => assert_eq!(line, 301, "{chunks} chunks"),
+            other => panic!("expected a long line error, got {other:?}"),
+        }
+    }
+    let at_limit = "7".repeat(MAX_LINE);
+    assert_eq!(
+        sum_reader(at_limit.as_bytes(), &scanner, policy).unwrap(),
+        77
+    );
+    std::fs::remove_file(path).unwrap();
+}
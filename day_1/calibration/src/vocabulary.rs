@@ -0,0 +1,227 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::scanner::DigitScanner;
+
+pub(crate) const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+pub(crate) const ENGLISH: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// A language whose words for zero to nine can be added to a vocabulary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    English,
+    German,
+    French,
+    Spanish,
+}
+
+impl Language {
+    /// The words for zero to nine, in order.
+    pub fn words(self) -> [&'static str; 10] {
+        match self {
+            Language::English => [
+                "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+            ],
+            Language::German => [
+                "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+            ],
+            Language::French => [
+                "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+            ],
+            Language::Spanish => [
+                "cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+            ],
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(code: &str) -> Result<Language, String> {
+        match code {
+            "en" => Ok(Language::English),
+            "de" => Ok(Language::German),
+            "fr" => Ok(Language::French),
+            "es" => Ok(Language::Spanish),
+            other => Err(format!(
+                "unknown language {other:?}, expected en, de, fr or es"
+            )),
+        }
+    }
+}
+
+/// The tokens a scanner recognises and the digit each one stands for.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Vocabulary {
+    tokens: Vec<(String, u32)>,
+    ignore_case: bool,
+}
+
+impl Vocabulary {
+    pub fn new() -> Vocabulary {
+        Vocabulary::default()
+    }
+
+    /// The ten ASCII digits.
+    pub fn digits() -> Vocabulary {
+        DIGITS
+            .iter()
+            .zip(0..)
+            .fold(Vocabulary::new(), |v, (t, d)| v.with_token(t, d))
+    }
+
+    /// ASCII digits plus the English words "one" to "nine", as in part 2.
+    pub fn english() -> Vocabulary {
+        ENGLISH
+            .iter()
+            .zip(1..)
+            .fold(Vocabulary::digits(), |v, (t, d)| v.with_token(t, d))
+    }
+
+    pub fn with_token(mut self, token: &str, digit: u32) -> Vocabulary {
+        self.tokens.push((token.to_string(), digit));
+        self
+    }
+
+    /// Adds the language's words for zero to nine.
+    pub fn with_language(self, language: Language) -> Vocabulary {
+        language
+            .words()
+            .iter()
+            .zip(0..)
+            .fold(self, |v, (t, d)| v.with_token(t, d))
+    }
+
+    pub fn with_vocabulary(mut self, other: &Vocabulary) -> Vocabulary {
+        self.tokens.extend(other.tokens.iter().cloned());
+        self
+    }
+
+    /// Matches ASCII letters regardless of case. Other characters, such as
+    /// the "ü" in "fünf", must still match exactly.
+    pub fn ignoring_case(mut self) -> Vocabulary {
+        self.ignore_case = true;
+        self
+    }
+
+    pub fn tokens(&self) -> &[(String, u32)] {
+        &self.tokens
+    }
+
+    pub fn scanner(&self) -> DigitScanner {
+        DigitScanner::build(
+            self.tokens.iter().map(|(t, d)| (t.as_str(), *d)),
+            self.ignore_case,
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum VocabularyError {
+    Malformed {
+        line: usize,
+        text: String,
+    },
+    NotADigit {
+        line: usize,
+        token: String,
+        value: u32,
+    },
+}
+
+impl fmt::Display for VocabularyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VocabularyError::Malformed { line, text } => {
+                write!(f, "line {line}: expected `token digit`, found {text:?}")
+            }
+            VocabularyError::NotADigit { line, token, value } => {
+                write!(f, "line {line}: {token:?} maps to {value}, not a digit")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VocabularyError {}
+
+/// Reads a token table with one `token digit` pair per line. Blank lines
+/// and lines starting with `#` are ignored.
+pub fn parse_vocabulary(text: &str) -> Result<Vocabulary, VocabularyError> {
+    let mut vocabulary = Vocabulary::new();
+    for (i, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let malformed = || VocabularyError::Malformed {
+            line: i + 1,
+            text: raw.to_string(),
+        };
+        let mut fields = line.split_whitespace();
+        let (Some(token), Some(value), None) = (fields.next(), fields.next(), fields.next()) else {
+            return Err(malformed());
+        };
+        let value: u32 = value.parse().map_err(|_| malformed())?;
+        if value > 9 {
+            return Err(VocabularyError::NotADigit {
+                line: i + 1,
+                token: token.to_string(),
+                value,
+            });
+        }
+        vocabulary = vocabulary.with_token(token, value);
+    }
+    Ok(vocabulary)
+}
+
+#[cfg(test)]
+#[test]
+fn languages_include_zero() {
+    let scanner = Vocabulary::new()
+        .with_language(Language::German)
+        .with_language(Language::Spanish)
+        .scanner();
+    let values: Vec<u32> = scanner.find_all("nullachtcero").map(|m| m.value).collect();
+    assert_eq!(values, [0, 8, 0]);
+    let (first, last) = scanner.first_and_last("xfünfzigunddrei").unwrap();
+    assert_eq!((first.value, first.start, last.value), (5, 1, 3));
+}
+
+#[test]
+fn ignoring_case() {
+    let exact = Vocabulary::english().scanner();
+    let folded = Vocabulary::english().ignoring_case().scanner();
+    assert_eq!(exact.first_and_last("TwoXnINe"), None);
+    let (first, last) = folded.first_and_last("TwoXnINe").unwrap();
+    assert_eq!((first.value, last.value), (2, 9));
+    assert_eq!(folded.find_all("EIGHTwo").count(), 2);
+}
+
+#[test]
+fn token_table() {
+    let text = "# roman numerals\nI 1\n\nV 5\n  ix   9\n";
+    let vocabulary = parse_vocabulary(text).unwrap();
+    assert_eq!(
+        vocabulary.tokens(),
+        [("I".into(), 1), ("V".into(), 5), ("ix".into(), 9)]
+    );
+    assert_eq!(
+        parse_vocabulary("one 1\nten 10"),
+        Err(VocabularyError::NotADigit {
+            line: 2,
+            token: "ten".into(),
+            value: 10
+        })
+    );
+    assert_eq!(
+        parse_vocabulary("one 1 2"),
+        Err(VocabularyError::Malformed {
+            line: 1,
+            text: "one 1 2".into()
+        })
+    );
+}
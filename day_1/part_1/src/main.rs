@@ -1,22 +1,36 @@
+use calibration::{
+    calibration_sum, sum_file_parallel, sum_reader, DigitScanner, MissingDigits, NoDigits,
+};
+
 fn main() {
     let i = include_str!("../input1.txt");
-    println!("{}", process(i));
+    let mut args = std::env::args().skip(1);
+    let mut policy = MissingDigits::default();
+    let mut file = None;
+    let mut chunks = 1;
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| panic!("{arg} needs a value"));
+        match arg.as_str() {
+            "--missing" => policy = value().parse().unwrap_or_else(|e| panic!("{e}")),
+            "--file" => file = Some(value()),
+            "--chunks" => chunks = value().parse().expect("number of chunks"),
+            other => panic!("unknown argument {other:?}"),
+        }
+    }
+    let scanner = DigitScanner::digits();
+    let sum = match file.as_deref() {
+        None => process(i, policy).map_err(|e| e.to_string()),
+        Some("-") => {
+            sum_reader(std::io::stdin().lock(), &scanner, policy).map_err(|e| e.to_string())
+        }
+        Some(path) => sum_file_parallel(path.as_ref(), &scanner, policy, chunks)
+            .map_err(|e| format!("{path}: {e}")),
+    };
+    println!("{}", sum.unwrap_or_else(|e| panic!("{e}")));
 }
 
-fn process(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|l| {
-            let mut it = l.chars().filter(|c| c.is_digit(10));
-            let first = it.next().unwrap();
-            let last = match it.last() {
-                Some(d) => d,
-                None => first,
-            };
-            let coords = format!("{}{}", first, last);
-            coords.parse::<u32>().unwrap()
-        })
-        .sum()
+fn process(input: &str, policy: MissingDigits) -> Result<u64, NoDigits> {
+    calibration_sum(input, &DigitScanner::digits(), policy)
 }
 
 #[cfg(test)]
@@ -27,5 +41,5 @@ pqr3stu8vwx
 a1b2c3d4e5f
 treb7uchet
 ";
-    assert_eq!(process(&s), 142);
+    assert_eq!(process(s, MissingDigits::Error), Ok(142));
 }
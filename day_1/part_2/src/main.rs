@@ -1,41 +1,67 @@
+use calibration::{
+    calibration_sum, line_reports, parse_vocabulary, render_csv, render_table, sum_file_parallel,
+    sum_reader, DigitScanner, Language, MissingDigits, NoDigits, Vocabulary,
+};
+
 fn main() {
     let s = include_str!("../input.txt");
-    println!("{}", process(s));
-}
-
-fn match_spelled_out(s: &str) -> Option<u32> {
-    let words = [
-        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-    ];
-
-    for j in 0..words.len() {
-        if words[j].len() <= s.len() && &s[..words[j].len()] == words[j] {
-            return Some(j as u32 + 1);
+    let mut args = std::env::args().skip(1);
+    let mut vocabulary = Vocabulary::english();
+    let mut policy = MissingDigits::default();
+    let mut file = None;
+    let mut chunks = 1;
+    let mut report = None;
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| panic!("{arg} needs a value"));
+        match arg.as_str() {
+            "--lang" => {
+                let language: Language = value().parse().unwrap_or_else(|e| panic!("{e}"));
+                vocabulary = vocabulary.with_language(language);
+            }
+            "--vocab" => {
+                let path = value();
+                let text = std::fs::read_to_string(&path)
+                    .unwrap_or_else(|e| panic!("reading {path}: {e}"));
+                let table = parse_vocabulary(&text).unwrap_or_else(|e| panic!("{path}: {e}"));
+                vocabulary = vocabulary.with_vocabulary(&table);
+            }
+            "--ignore-case" => vocabulary = vocabulary.ignoring_case(),
+            "--missing" => policy = value().parse().unwrap_or_else(|e| panic!("{e}")),
+            "--file" => file = Some(value()),
+            "--chunks" => chunks = value().parse().expect("number of chunks"),
+            "--table" | "--csv" => report = Some(arg.clone()),
+            other => panic!("unknown argument {other:?}"),
         }
     }
-    None
+    let scanner = vocabulary.scanner();
+    if let Some(format) = report {
+        let text = match file.as_deref() {
+            None => s.to_string(),
+            Some("-") => std::io::read_to_string(std::io::stdin()).expect("readable stdin"),
+            Some(path) => {
+                std::fs::read_to_string(path).unwrap_or_else(|e| panic!("reading {path}: {e}"))
+            }
+        };
+        let reports = line_reports(&text, &DigitScanner::digits(), &scanner);
+        match format.as_str() {
+            "--table" => print!("{}", render_table(&reports)),
+            _ => print!("{}", render_csv(&reports)),
+        }
+        return;
+    }
+    let sum = match file.as_deref() {
+        None => process(s, &scanner, policy).map_err(|e| e.to_string()),
+        Some("-") => {
+            sum_reader(std::io::stdin().lock(), &scanner, policy).map_err(|e| e.to_string())
+        }
+        Some(path) => sum_file_parallel(path.as_ref(), &scanner, policy, chunks)
+            .map_err(|e| format!("{path}: {e}")),
+    };
+    println!("{}", sum.unwrap_or_else(|e| panic!("{e}")));
 }
 
-fn process(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| {
-            let mut it = (0..line.len()).filter_map(|i| {
-                let c = line[i..].chars().next().unwrap();
-                if c.is_digit(10) {
-                    c.to_digit(10)
-                } else {
-                    match_spelled_out(&line[i..])
-                }
-            });
-            let first = it.next().unwrap();
-            let last = match it.last() {
-                Some(d) => d,
-                None => first,
-            };
-            format!("{first}{last}").parse::<u32>().unwrap()
-        })
-        .sum()
+fn process(input: &str, scanner: &DigitScanner, policy: MissingDigits) -> Result<u64, NoDigits> {
+    calibration_sum(input, scanner, policy)
 }
 
 #[cfg(test)]
@@ -48,5 +74,8 @@ xtwone3four
 4nineeightseven2
 zoneight234
 7pqrstsixteen";
-    assert_eq!(process(s), 281);
+    assert_eq!(
+        process(s, &DigitScanner::english(), MissingDigits::Error),
+        Ok(281)
+    );
 }
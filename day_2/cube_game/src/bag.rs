@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+
+use nom::bytes::complete::tag;
+use nom::character::complete::{alpha1, u32};
+use nom::combinator::all_consuming;
+use nom::multi::separated_list1;
+use nom::sequence::separated_pair;
+
+use crate::game::Game;
+
+/// Blocks of each colour in the bag; colours it does not list have none.
+pub type Bag = BTreeMap<String, u32>;
+
+/// The bag the Elf asks about in part 1.
+pub fn puzzle_bag() -> Bag {
+    Bag::from([
+        ("red".to_string(), 12),
+        ("green".to_string(), 13),
+        ("blue".to_string(), 14),
+    ])
+}
+
+/// The first time a game showed more blocks of a colour than the bag holds.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Violation {
+    pub round: usize,
+    pub colour: String,
+    pub shown: u32,
+    pub available: u32,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct QueryResult {
+    pub feasible: Vec<u32>,
+    pub infeasible: Vec<(u32, Violation)>,
+}
+
+/// Rounds are checked in order, and each round's blocks in the order listed.
+pub fn first_violation(game: &Game, bag: &Bag) -> Option<Violation> {
+    game.rounds.iter().enumerate().find_map(|(i, round)| {
+        round.blocks.iter().find_map(|&(ref colour, shown)| {
+            let available = bag.get(colour).copied().unwrap_or(0);
+            (shown > available).then(|| Violation {
+                round: i + 1,
+                colour: colour.clone(),
+                shown,
+                available,
+            })
+        })
+    })
+}
+
+pub fn query(games: &[Game], bag: &Bag) -> QueryResult {
+    let mut result = QueryResult::default();
+    for game in games {
+        match first_violation(game, bag) {
+            None => result.feasible.push(game.id),
+            Some(violation) => result.infeasible.push((game.id, violation)),
+        }
+    }
+    result
+}
+
+pub fn render_query(bag: &Bag, result: &QueryResult) -> String {
+    let bag: Vec<String> = bag.iter().map(|(c, n)| format!("{c}={n}")).collect();
+    let mut out = format!(
+        "bag {}: {} feasible, id sum {}\n",
+        bag.join(","),
+        result.feasible.len(),
+        result.feasible.iter().sum::<u32>(),
+    );
+    if !result.feasible.is_empty() {
+        let ids: Vec<String> = result.feasible.iter().map(u32::to_string).collect();
+        out += &format!("  feasible: {}\n", ids.join(" "));
+    }
+    for (id, v) in &result.infeasible {
+        out += &format!(
+            "  game {id}: round {} shows {} {} but the bag holds {}\n",
+            v.round, v.shown, v.colour, v.available
+        );
+    }
+    out
+}
+
+/// Parses a bag given as `colour=count` pairs, such as `red=12,green=13`.
+pub fn parse_bag(spec: &str) -> Result<Bag, String> {
+    let pair = separated_pair(alpha1, tag("="), u32);
+    let (_, pairs) = all_consuming(separated_list1(tag(","), pair))(spec)
+        .map_err(|e: nom::Err<nom::error::Error<&str>>| format!("invalid bag {spec:?}: {e}"))?;
+    Ok(pairs.into_iter().map(|(c, n)| (c.to_string(), n)).collect())
+}
+
+#[cfg(test)]
+#[test]
+fn query_reports_violations() {
+    let s = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red";
+    let games = crate::game::parse_games(s).unwrap();
+    let bag = puzzle_bag();
+    assert_eq!(
+        query(&games, &bag),
+        QueryResult {
+            feasible: vec![1],
+            infeasible: vec![
+                (
+                    3,
+                    Violation {
+                        round: 1,
+                        colour: "red".into(),
+                        shown: 20,
+                        available: 12
+                    }
+                ),
+                (
+                    4,
+                    Violation {
+                        round: 3,
+                        colour: "blue".into(),
+                        shown: 15,
+                        available: 14
+                    }
+                )
+            ]
+        }
+    );
+    let roomy = parse_bag("red=20,green=13,blue=15").unwrap();
+    assert_eq!(query(&games, &roomy).feasible, [1, 3, 4]);
+    let no_green = parse_bag("red=20,blue=15").unwrap();
+    assert_eq!(
+        first_violation(&games[0], &no_green).unwrap().colour,
+        "green"
+    );
+}
+
+#[test]
+fn violations_follow_input_order() {
+    let games =
+        crate::game::parse_games("Game 1: 20 red, 15 blue\nGame 2: 3 blue, 4 blue").unwrap();
+    let bag = parse_bag("red=12,blue=14").unwrap();
+    let violation = first_violation(&games[0], &bag).unwrap();
+    assert_eq!((violation.colour.as_str(), violation.shown), ("red", 20));
+    assert_eq!(first_violation(&games[1], &bag), None);
+}
+
+#[test]
+fn rejects_bad_bags() {
+    for spec in ["", "red", "red=", "red=1,", "red=1;blue=2"] {
+        assert!(parse_bag(spec).is_err(), "{spec:?}");
+    }
+}
@@ -0,0 +1,292 @@
+use std::cmp::max;
+use std::fmt;
+
+use nom::bytes::complete::tag;
+use nom::character::complete::{alpha1, char, space0, space1, u32};
+use nom::combinator::all_consuming;
+use nom::IResult;
+
+use nom::multi::{many0, many1, separated_list1};
+use nom::sequence::{delimited, pair, preceded, separated_pair};
+
+use crate::bag::Bag;
+
+/// The blocks shown in a round, in the order they were listed. A colour
+/// listed twice stays as two separate blocks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    pub blocks: Vec<(String, u32)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Round>,
+}
+
+impl Game {
+    /// The smallest bag that could have produced every round.
+    pub fn min_bag(&self) -> Bag {
+        self.rounds.iter().fold(Bag::new(), |mut acc, r| {
+            r.blocks.iter().for_each(|&(ref colour, n)| {
+                let needed = acc.entry(colour.clone()).or_insert(0);
+                *needed = max(n, *needed);
+            });
+            acc
+        })
+    }
+
+    /// Product of the blocks needed of each colour, counting colours the
+    /// game never showed as zero, or `None` if it overflows a `u64`.
+    pub fn power(&self, colours: &[String]) -> Option<u64> {
+        let min_bag = self.min_bag();
+        colours
+            .iter()
+            .map(|colour| min_bag.get(colour).copied().unwrap_or(0))
+            .try_fold(1u64, |acc, n| acc.checked_mul(u64::from(n)))
+    }
+}
+
+impl fmt::Display for Round {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let blocks: Vec<String> = self
+            .blocks
+            .iter()
+            .map(|(colour, n)| format!("{n} {colour}"))
+            .collect();
+        write!(f, "{}", blocks.join(", "))
+    }
+}
+
+/// Writes the canonical record: single spaces and no trailing separators.
+/// Blocks keep their order and counts.
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rounds: Vec<String> = self.rounds.iter().map(Round::to_string).collect();
+        write!(f, "Game {}: {}", self.id, rounds.join("; "))
+    }
+}
+
+/// A line that is not a game record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub text: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: not a game record: {:?}", self.line, self.text)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Every colour shown in any of the games, in alphabetical order.
+pub fn colours(games: &[Game]) -> Vec<String> {
+    let mut colours: Vec<String> = games
+        .iter()
+        .flat_map(|g| &g.rounds)
+        .flat_map(|r| r.blocks.iter().map(|(colour, _)| colour.clone()))
+        .collect();
+    colours.sort_unstable();
+    colours.dedup();
+    colours
+}
+
+/// Parses one game per line, skipping blank lines.
+pub fn parse_games(input: &str) -> Result<Vec<Game>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| match all_consuming(parse_game)(line) {
+            Ok((_, game)) => Ok(game),
+            Err(_) => Err(ParseError {
+                line: i + 1,
+                text: line.to_string(),
+            }),
+        })
+        .collect()
+}
+
+/// Reads every game in `input` and writes it back in canonical form, which
+/// changes only spacing and separators.
+pub fn format_games(input: &str) -> Result<String, ParseError> {
+    Ok(parse_games(input)?
+        .iter()
+        .map(|game| format!("{game}\n"))
+        .collect())
+}
+
+/// Parses a game record, tolerating runs of spaces, empty rounds and
+/// trailing semicolons.
+pub fn parse_game(input: &str) -> IResult<&str, Game> {
+    let mut header_parser = delimited(
+        pair(space0, tag("Game")),
+        preceded(space1, u32),
+        pair(space0, char(':')),
+    );
+    let (game_string, id) = header_parser(input)?;
+    let semicolons = || many1(delimited(space0, char(';'), space0));
+    let (remaining, rounds) = delimited(
+        many0(semicolons()),
+        separated_list1(semicolons(), round),
+        pair(many0(semicolons()), space0),
+    )(game_string)?;
+    Ok((remaining, Game { id, rounds }))
+}
+
+fn round(input: &str) -> IResult<&str, Round> {
+    let comma = delimited(space0, char(','), space0);
+    let (remaining, blocks) = preceded(space0, separated_list1(comma, block))(input)?;
+    let blocks = blocks
+        .into_iter()
+        .map(|(n, colour)| (colour.to_string(), n))
+        .collect();
+    Ok((remaining, Round { blocks }))
+}
+
+fn block(input: &str) -> IResult<&str, (u32, &str)> {
+    separated_pair(u32, space1, alpha1)(input)
+}
+
+#[cfg(test)]
+#[test]
+fn debug_parse_input() {
+    let s = include_str!("../input.txt");
+    for l in s.lines() {
+        let g = parse_game(l);
+        assert!(g.is_ok(), "{g:?}");
+    }
+}
+
+#[test]
+fn debug_parse_example() {
+    for l in crate::EXAMPLE.lines() {
+        let g = parse_game(l);
+        assert!(g.is_ok(), "{g:?}");
+    }
+}
+
+#[test]
+fn debug_parse_game() {
+    let s = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
+    let (_, game) = parse_game(s).unwrap();
+    assert_eq!(game.id, 1);
+    assert_eq!(game.rounds.len(), 3);
+}
+
+#[test]
+fn debug_parse_round() {
+    let s = "3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
+    let (remaining, r) = round(s).unwrap();
+    assert_eq!(remaining, "; 1 red, 2 green, 6 blue; 2 green");
+    assert_eq!(r.blocks, [("blue".into(), 3), ("red".into(), 4)]);
+}
+
+#[test]
+fn any_colour_parses() {
+    let (_, game) = parse_game("Game 7: 2 purple, 1 red; 3 purple").unwrap();
+    assert_eq!(
+        game.rounds[0].blocks,
+        [("purple".into(), 2), ("red".into(), 1)]
+    );
+    assert_eq!(game.rounds[1].blocks, [("purple".into(), 3)]);
+    assert_eq!(
+        game.min_bag(),
+        Bag::from([("purple".into(), 3), ("red".into(), 1)])
+    );
+    assert_eq!(game.power(&colours(std::slice::from_ref(&game))), Some(3));
+    assert_eq!(
+        game.power(&["purple".into(), "red".into(), "green".into()]),
+        Some(0)
+    );
+}
+
+#[test]
+fn formats_messy_records() {
+    let messy = "Game 1:3 blue ,4 red;;  1 red,2 green,   6 blue; 2 green;
+  Game  2 :  1 blue; \n\n";
+    let expected = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue
+";
+    assert_eq!(format_games(messy), Ok(expected.to_string()));
+}
+
+#[test]
+fn round_trips_through_canonical_form() {
+    let input = include_str!("../input.txt");
+    let games = parse_games(input).unwrap();
+    let printed = format_games(input).unwrap();
+    assert_eq!(parse_games(&printed).unwrap(), games);
+    assert_eq!(format_games(&printed).unwrap(), printed);
+}
+
+#[test]
+fn canonical_form_keeps_block_order_and_counts() {
+    let messy = "Game 1:  20 red ,15 blue;\nGame 2: 3 blue,4 blue ;;\n";
+    let printed = format_games(messy).unwrap();
+    assert_eq!(printed, "Game 1: 20 red, 15 blue\nGame 2: 3 blue, 4 blue\n");
+    let blocks: Vec<Vec<(String, u32)>> = parse_games(&printed)
+        .unwrap()
+        .into_iter()
+        .flat_map(|g| g.rounds)
+        .map(|r| r.blocks)
+        .collect();
+    assert_eq!(
+        blocks,
+        [
+            vec![("red".into(), 20), ("blue".into(), 15)],
+            vec![("blue".into(), 3), ("blue".into(), 4)]
+        ]
+    );
+}
+
+#[test]
+fn rejects_leftover_text() {
+    for line in [
+        "Game 1: 3 blue, 4 red x",
+        "Game 1: 3 blue 4 red",
+        "Game 1:",
+        "3 blue",
+    ] {
+        assert_eq!(
+            parse_games(line),
+            Err(ParseError {
+                line: 1,
+                text: line.to_string()
+            })
+        );
+    }
+}
+
+#[test]
+fn repeated_colours_stay_separate_blocks() {
+    let (_, game) = parse_game("Game 2: 3 blue, 4 blue").unwrap();
+    assert_eq!(
+        game.rounds[0].blocks,
+        [("blue".into(), 3), ("blue".into(), 4)]
+    );
+    assert_eq!(game.min_bag(), Bag::from([("blue".into(), 4)]));
+}
+
+#[test]
+fn power_beyond_u32() {
+    let record = |n: usize| {
+        let blocks: Vec<String> = (b'a'..)
+            .take(n)
+            .map(|c| format!("16 {}", c as char))
+            .collect();
+        parse_game(&format!("Game 1: {}", blocks.join(", ")))
+            .unwrap()
+            .1
+    };
+    let game = record(8);
+    assert_eq!(
+        game.power(&colours(std::slice::from_ref(&game))),
+        Some(1 << 32)
+    );
+    let game = record(16);
+    assert_eq!(game.power(&colours(std::slice::from_ref(&game))), None);
+}
@@ -0,0 +1,231 @@
+use std::fmt;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::game::{Game, Round};
+
+/// Shape of the random game records to produce.
+#[derive(Debug, Clone)]
+pub struct GeneratorConfig {
+    pub games: usize,
+    pub min_rounds: usize,
+    pub max_rounds: usize,
+    pub colours: Vec<String>,
+    pub max_count: u32,
+    pub seed: u64,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            games: 100,
+            min_rounds: 1,
+            max_rounds: 6,
+            colours: ["red", "green", "blue"].map(String::from).to_vec(),
+            max_count: 20,
+            seed: 0,
+        }
+    }
+}
+
+/// A generator setting that would produce no valid games.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// Every game needs at least one round.
+    NoRounds,
+    Rounds {
+        min: usize,
+        max: usize,
+    },
+    NoColours,
+    /// Colours must be alphabetic words to parse back.
+    BadColour(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::NoRounds => write!(f, "games need at least one round"),
+            ConfigError::Rounds { min, max } => {
+                write!(f, "minimum of {min} rounds exceeds maximum of {max}")
+            }
+            ConfigError::NoColours => write!(f, "no colours to choose from"),
+            ConfigError::BadColour(c) => write!(f, "colour {c:?} is not an alphabetic word"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl GeneratorConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.min_rounds == 0 || self.max_rounds == 0 {
+            return Err(ConfigError::NoRounds);
+        }
+        if self.min_rounds > self.max_rounds {
+            return Err(ConfigError::Rounds {
+                min: self.min_rounds,
+                max: self.max_rounds,
+            });
+        }
+        if self.colours.is_empty() {
+            return Err(ConfigError::NoColours);
+        }
+        match self
+            .colours
+            .iter()
+            .find(|c| c.is_empty() || !c.chars().all(|ch| ch.is_ascii_alphabetic()))
+        {
+            Some(c) => Err(ConfigError::BadColour(c.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+fn random_round(config: &GeneratorConfig, rng: &mut StdRng) -> Round {
+    let n_colours = rng.gen_range(1..=config.colours.len());
+    let blocks = config
+        .colours
+        .choose_multiple(rng, n_colours)
+        .map(|colour| (colour.clone(), rng.gen_range(0..=config.max_count)))
+        .collect();
+    Round { blocks }
+}
+
+/// Random but valid games, numbered from 1.
+pub fn generate_games(config: &GeneratorConfig) -> Result<Vec<Game>, ConfigError> {
+    config.validate()?;
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    Ok((1..=config.games as u32)
+        .map(|id| {
+            let n_rounds = rng.gen_range(config.min_rounds..=config.max_rounds);
+            let rounds = (0..n_rounds)
+                .map(|_| random_round(config, &mut rng))
+                .collect();
+            Game { id, rounds }
+        })
+        .collect())
+}
+
+fn gap(rng: &mut StdRng, min: usize, max: usize) -> String {
+    " ".repeat(rng.gen_range(min..=max))
+}
+
+/// Writes a game with the untidy spacing and separators the parser accepts.
+pub fn render_messy(game: &Game, rng: &mut StdRng) -> String {
+    let mut out = format!(
+        "{}Game{}{}{}:",
+        gap(rng, 0, 2),
+        gap(rng, 1, 3),
+        game.id,
+        gap(rng, 0, 2)
+    );
+    for (i, round) in game.rounds.iter().enumerate() {
+        if i > 0 {
+            out += &format!("{};{}", gap(rng, 0, 2), gap(rng, 0, 2));
+        }
+        for (j, (colour, n)) in round.blocks.iter().enumerate() {
+            if j > 0 {
+                out += &format!("{},", gap(rng, 0, 2));
+            }
+            out += &format!("{}{n}{}{colour}", gap(rng, 0, 2), gap(rng, 1, 3));
+        }
+    }
+    if rng.gen_bool(0.5) {
+        out += ";";
+    }
+    out + &gap(rng, 0, 3)
+}
+
+/// Lines that look like game records but are not, and why.
+pub const MALFORMED: [(&str, &str); 14] = [
+    ("", "empty"),
+    ("Game 1: 3 blue 4 red", "missing comma"),
+    ("Game 1: 3 blue,, 4 red", "doubled comma"),
+    ("Game 1: 3 blue, 4 red,", "trailing comma"),
+    ("Game 1: , 3 blue", "leading comma"),
+    ("Game 1:", "no rounds"),
+    ("Game 1: ;", "only separators"),
+    ("Game: 3 blue", "missing id"),
+    ("Game x: 3 blue", "non-numeric id"),
+    ("game 1: 3 blue", "lowercase header"),
+    ("Game 1 3 blue", "missing colon"),
+    ("Game 1: blue", "missing count"),
+    ("Game 1: 3", "missing colour"),
+    ("Game 1: -3 blue", "negative count"),
+];
+
+#[test]
+fn generated_games_round_trip() {
+    let config = GeneratorConfig {
+        colours: ["red", "green", "blue", "cyan", "magenta"]
+            .map(String::from)
+            .to_vec(),
+        ..GeneratorConfig::default()
+    };
+    let games = generate_games(&config).unwrap();
+    assert_eq!(games.len(), 100);
+    let canonical: String = games.iter().map(|g| format!("{g}\n")).collect();
+    assert_eq!(crate::game::parse_games(&canonical).unwrap(), games);
+    let mut rng = StdRng::seed_from_u64(1);
+    let messy: String = games
+        .iter()
+        .map(|g| render_messy(g, &mut rng) + "\n")
+        .collect();
+    assert_eq!(crate::game::parse_games(&messy).unwrap(), games);
+}
+
+#[test]
+fn generator_is_deterministic() {
+    let config = GeneratorConfig::default();
+    assert_eq!(generate_games(&config), generate_games(&config));
+    let other_seed = GeneratorConfig {
+        seed: 1,
+        ..config.clone()
+    };
+    assert_ne!(generate_games(&config), generate_games(&other_seed));
+}
+
+#[test]
+fn bad_configs_are_rejected() {
+    let with = |f: fn(&mut GeneratorConfig)| {
+        let mut config = GeneratorConfig::default();
+        f(&mut config);
+        generate_games(&config)
+    };
+    assert_eq!(with(|c| c.max_rounds = 0), Err(ConfigError::NoRounds));
+    assert_eq!(with(|c| c.min_rounds = 0), Err(ConfigError::NoRounds));
+    assert_eq!(
+        with(|c| c.min_rounds = 7),
+        Err(ConfigError::Rounds { min: 7, max: 6 })
+    );
+    assert_eq!(with(|c| c.colours.clear()), Err(ConfigError::NoColours));
+    assert_eq!(
+        with(|c| c.colours.push("light blue".into())),
+        Err(ConfigError::BadColour("light blue".into()))
+    );
+    assert_eq!(
+        with(|c| c.colours.push(String::new())),
+        Err(ConfigError::BadColour(String::new()))
+    );
+    let fixed = with(|c| (c.min_rounds, c.max_rounds) = (3, 3)).unwrap();
+    assert!(fixed.iter().all(|g| g.rounds.len() == 3));
+}
+
+#[test]
+fn zero_counts_and_trailing_whitespace_parse() {
+    let (_, game) = crate::game::parse_game("Game 4: 0 red, 0 blue \t").unwrap();
+    assert_eq!(
+        game.rounds[0].blocks,
+        [("red".into(), 0), ("blue".into(), 0)]
+    );
+    assert_eq!(game.to_string(), "Game 4: 0 red, 0 blue");
+}
+
+#[test]
+fn malformed_lines_are_rejected() {
+    for (line, why) in MALFORMED {
+        let parsed = nom::combinator::all_consuming(crate::game::parse_game)(line);
+        assert!(parsed.is_err(), "{why}: {line:?} parsed as {parsed:?}");
+    }
+}
@@ -0,0 +1,134 @@
+mod bag;
+mod game;
+mod generate;
+mod stats;
+
+use bag::{parse_bag, puzzle_bag, query, render_query};
+use game::{colours, format_games, parse_games, Game};
+use generate::{generate_games, render_messy, GeneratorConfig, MALFORMED};
+use rand::{rngs::StdRng, SeedableRng};
+use stats::{game_stats, render_csv, render_json};
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["--format"] => {
+            let messy = std::io::read_to_string(std::io::stdin()).expect("readable stdin");
+            print!("{}", format_games(&messy).unwrap_or_else(|e| panic!("{e}")));
+            return;
+        }
+        ["--generate", games, rest @ ..] => {
+            let mut config = GeneratorConfig {
+                games: games.parse().expect("number of games"),
+                ..GeneratorConfig::default()
+            };
+            let mut messy = false;
+            let mut rest = rest.iter();
+            while let Some(&arg) = rest.next() {
+                match arg {
+                    "--seed" => {
+                        let seed = rest.next().unwrap_or_else(|| panic!("{arg} needs a value"));
+                        config.seed = seed.parse().expect("seed");
+                    }
+                    "--messy" => messy = true,
+                    other => panic!("unknown argument {other:?}"),
+                }
+            }
+            let mut rng = StdRng::seed_from_u64(config.seed);
+            for game in generate_games(&config).unwrap_or_else(|e| panic!("{e}")) {
+                if messy {
+                    println!("{}", render_messy(&game, &mut rng));
+                } else {
+                    println!("{game}");
+                }
+            }
+            return;
+        }
+        ["--malformed"] => {
+            for (line, _) in MALFORMED {
+                println!("{line}");
+            }
+            return;
+        }
+        _ => {}
+    }
+    let input = include_str!("../input.txt");
+    let games = parse_games(input).unwrap_or_else(|e| panic!("{e}"));
+    let mut args = std::env::args().skip(1);
+    let mut bags = Vec::new();
+    let mut stats_format = None;
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| panic!("{arg} needs a value"));
+        match arg.as_str() {
+            "--bag" => bags.push(parse_bag(&value()).unwrap_or_else(|e| panic!("{e}"))),
+            "--stats" => stats_format = Some(value()),
+            other => panic!("unknown argument {other:?}"),
+        }
+    }
+    for bag in &bags {
+        print!("{}", render_query(bag, &query(&games, bag)));
+    }
+    if let Some(format) = &stats_format {
+        let colours = colours(&games);
+        let stats = game_stats(&games, &colours);
+        match format.as_str() {
+            "csv" => print!("{}", render_csv(&stats, &colours)),
+            "json" => print!("{}", render_json(&stats, &colours)),
+            other => panic!("unknown stats format {other:?}, expected csv or json"),
+        }
+    }
+    if bags.is_empty() && stats_format.is_none() {
+        println!("{}", feasible_id_sum(&games));
+        println!("{}", power_sum(&games));
+    }
+}
+
+fn feasible_id_sum(games: &[Game]) -> u32 {
+    query(games, &puzzle_bag()).feasible.iter().sum()
+}
+
+fn power_sum(games: &[Game]) -> u64 {
+    let colours = colours(games);
+    games
+        .iter()
+        .map(|game| {
+            game.power(&colours)
+                .unwrap_or_else(|| panic!("power of game {} overflows", game.id))
+        })
+        .try_fold(0u64, u64::checked_add)
+        .expect("sum of powers overflows")
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+#[test]
+fn example_1() {
+    assert_eq!(feasible_id_sum(&parse_games(EXAMPLE).unwrap()), 8);
+}
+
+#[test]
+fn example_2() {
+    assert_eq!(power_sum(&parse_games(EXAMPLE).unwrap()), 2286);
+}
+
+#[test]
+fn part_1() {
+    let s = include_str!("../input.txt");
+    assert_eq!(feasible_id_sum(&parse_games(s).unwrap()), 2551);
+}
+
+#[test]
+fn part_2() {
+    let s = include_str!("../input.txt");
+    assert_eq!(power_sum(&parse_games(s).unwrap()), 62811);
+}
@@ -0,0 +1,99 @@
+use crate::bag::Bag;
+use crate::game::Game;
+
+/// What one game needed and how long it ran.
+#[derive(Debug, PartialEq, Eq)]
+pub struct GameStats {
+    pub id: u32,
+    pub rounds: usize,
+    pub min_bag: Bag,
+    /// `None` if the power overflows a `u64`.
+    pub power: Option<u64>,
+}
+
+/// Stats for every game, taking the power over all `colours`.
+pub fn game_stats(games: &[Game], colours: &[String]) -> Vec<GameStats> {
+    games
+        .iter()
+        .map(|game| GameStats {
+            id: game.id,
+            rounds: game.rounds.len(),
+            min_bag: game.min_bag(),
+            power: game.power(colours),
+        })
+        .collect()
+}
+
+fn min_counts<'a>(stats: &'a GameStats, colours: &'a [String]) -> impl Iterator<Item = u32> + 'a {
+    colours
+        .iter()
+        .map(|colour| stats.min_bag.get(colour).copied().unwrap_or(0))
+}
+
+/// One row per game with a `min_<colour>` column for each colour.
+pub fn render_csv(stats: &[GameStats], colours: &[String]) -> String {
+    let mut header = vec!["id".to_string(), "rounds".to_string()];
+    header.extend(colours.iter().map(|c| format!("min_{c}")));
+    header.push("power".to_string());
+    let mut out = header.join(",") + "\n";
+    for s in stats {
+        let mut row = vec![s.id.to_string(), s.rounds.to_string()];
+        row.extend(min_counts(s, colours).map(|n| n.to_string()));
+        row.push(s.power.map_or(String::new(), |p| p.to_string()));
+        out += &row.join(",");
+        out += "\n";
+    }
+    out
+}
+
+/// A JSON array with one object per game. Colours are parsed as alphabetic
+/// words, so they never need escaping.
+pub fn render_json(stats: &[GameStats], colours: &[String]) -> String {
+    let objects: Vec<String> = stats
+        .iter()
+        .map(|s| {
+            let min_bag: Vec<String> = colours
+                .iter()
+                .zip(min_counts(s, colours))
+                .map(|(c, n)| format!("\"{c}\":{n}"))
+                .collect();
+            format!(
+                "  {{\"id\":{},\"rounds\":{},\"min_bag\":{{{}}},\"power\":{}}}",
+                s.id,
+                s.rounds,
+                min_bag.join(","),
+                s.power.map_or("null".to_string(), |p| p.to_string())
+            )
+        })
+        .collect();
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+#[cfg(test)]
+fn example_stats() -> (Vec<GameStats>, Vec<String>) {
+    let games = crate::game::parse_games(crate::EXAMPLE).unwrap();
+    let colours = crate::game::colours(&games);
+    (game_stats(&games[..2], &colours), colours)
+}
+
+#[test]
+fn example_csv() {
+    let (stats, colours) = example_stats();
+    let expected = "\
+id,rounds,min_blue,min_green,min_red,power
+1,3,6,2,4,48
+2,3,4,3,1,12
+";
+    assert_eq!(render_csv(&stats, &colours), expected);
+}
+
+#[test]
+fn example_json() {
+    let (stats, colours) = example_stats();
+    let expected = r#"[
+  {"id":1,"rounds":3,"min_bag":{"blue":6,"green":2,"red":4},"power":48},
+  {"id":2,"rounds":3,"min_bag":{"blue":4,"green":3,"red":1},"power":12}
+]
+"#;
+    assert_eq!(render_json(&stats, &colours), expected);
+}
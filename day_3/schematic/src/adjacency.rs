@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use glam::IVec2;
+
+use crate::{Diagram, Number, Symbol, SymbolClass};
+
+/// A symbol and every number touching it, in reading order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolParts {
+    pub symbol: Symbol,
+    pub numbers: Vec<Number>,
+}
+
+impl SymbolParts {
+    pub fn sum(&self) -> u64 {
+        self.numbers.iter().map(|n| u64::from(n.value)).sum()
+    }
+
+    /// The product of the numbers, or `None` if it overflows a `u64`.
+    pub fn product(&self) -> Option<u64> {
+        self.numbers
+            .iter()
+            .try_fold(1u64, |acc, n| acc.checked_mul(u64::from(n.value)))
+    }
+}
+
+/// How many numbers a symbol must touch to be selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbours {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl Neighbours {
+    pub fn matches(self, count: usize) -> bool {
+        match self {
+            Neighbours::Exactly(k) => count == k,
+            Neighbours::AtLeast(k) => count >= k,
+        }
+    }
+}
+
+/// Which numbers touch which symbols of a class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Adjacency {
+    /// Every symbol in the class, in reading order, even those touching
+    /// no numbers.
+    pub symbols: Vec<SymbolParts>,
+    /// Numbers touching no symbol in the class.
+    pub unattached: Vec<Number>,
+}
+
+impl Adjacency {
+    pub fn new(diagram: &Diagram, class: &SymbolClass) -> Self {
+        let mut symbols: Vec<SymbolParts> = diagram
+            .symbols_in(class)
+            .map(|&symbol| SymbolParts {
+                symbol,
+                numbers: Vec::new(),
+            })
+            .collect();
+        let index: HashMap<IVec2, usize> = symbols
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.symbol.pos, i))
+            .collect();
+        let mut unattached = Vec::new();
+        for number in diagram.numbers() {
+            let mut attached = false;
+            for i in number.neighbours().filter_map(|pos| index.get(&pos)) {
+                symbols[*i].numbers.push(*number);
+                attached = true;
+            }
+            if !attached {
+                unattached.push(*number);
+            }
+        }
+        Self {
+            symbols,
+            unattached,
+        }
+    }
+
+    pub fn matching(&self, neighbours: Neighbours) -> impl Iterator<Item = &SymbolParts> {
+        self.symbols
+            .iter()
+            .filter(move |s| neighbours.matches(s.numbers.len()))
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+#[test]
+fn example_gears() {
+    let diagram = crate::parse_diagram(EXAMPLE).unwrap();
+    let adjacency = Adjacency::new(&diagram, &SymbolClass::one_of("*"));
+    let ratios: Vec<Option<u64>> = adjacency
+        .matching(Neighbours::Exactly(2))
+        .map(SymbolParts::product)
+        .collect();
+    assert_eq!(ratios, [Some(16345), Some(451490)]);
+    let lonely: Vec<u32> = adjacency
+        .matching(Neighbours::Exactly(1))
+        .flat_map(|s| s.numbers.iter().map(|n| n.value))
+        .collect();
+    assert_eq!(lonely, [617]);
+    assert_eq!(adjacency.matching(Neighbours::AtLeast(1)).count(), 3);
+    let unattached: Vec<u32> = adjacency.unattached.iter().map(|n| n.value).collect();
+    assert_eq!(unattached, [114, 633, 58, 592, 664]);
+}
+
+#[test]
+fn unattached_to_any_symbol() {
+    let diagram = crate::parse_diagram(EXAMPLE).unwrap();
+    let adjacency = Adjacency::new(&diagram, &SymbolClass::Any);
+    let unattached: Vec<u32> = adjacency.unattached.iter().map(|n| n.value).collect();
+    assert_eq!(unattached, [114, 58]);
+    let sums: Vec<u64> = adjacency.symbols.iter().map(SymbolParts::sum).collect();
+    assert_eq!(sums, [502, 633, 617, 592, 664, 1353]);
+}
+
+#[test]
+fn products_are_checked() {
+    let diagram = crate::parse_diagram("4000000000*4000000000\n.........*3000000000.").unwrap();
+    let adjacency = Adjacency::new(&diagram, &SymbolClass::Any);
+    let products: Vec<Option<u64>> = adjacency.symbols.iter().map(SymbolParts::product).collect();
+    assert_eq!(products, [None, Some(12_000_000_000_000_000_000)]);
+}
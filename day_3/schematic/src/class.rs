@@ -0,0 +1,35 @@
+use std::str::FromStr;
+
+/// Which symbol characters a query cares about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SymbolClass {
+    /// Every symbol: anything that is neither a digit nor a dot.
+    Any,
+    OneOf(Vec<char>),
+}
+
+impl SymbolClass {
+    pub fn one_of(chars: &str) -> SymbolClass {
+        SymbolClass::OneOf(chars.chars().collect())
+    }
+
+    pub fn contains(&self, ch: char) -> bool {
+        match self {
+            SymbolClass::Any => true,
+            SymbolClass::OneOf(chars) => chars.contains(&ch),
+        }
+    }
+}
+
+impl FromStr for SymbolClass {
+    type Err = String;
+
+    /// `any`, or the characters of the class written out, such as `*@`.
+    fn from_str(s: &str) -> Result<SymbolClass, String> {
+        match s {
+            "" => Err("empty symbol class".to_string()),
+            "any" => Ok(SymbolClass::Any),
+            chars => Ok(SymbolClass::one_of(chars)),
+        }
+    }
+}
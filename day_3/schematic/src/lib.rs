@@ -0,0 +1,169 @@
+mod adjacency;
+mod class;
+mod parse;
+mod render;
+
+use glam::IVec2;
+
+pub use adjacency::{Adjacency, Neighbours, SymbolParts};
+pub use class::SymbolClass;
+pub use parse::{parse_diagram, ParseError};
+pub use render::{cell_styles, render_ansi, render_svg, Style};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagramChar {
+    /// Anything that is neither a digit nor a dot.
+    Symbol(char),
+    Digit(char),
+    Nothing,
+}
+
+impl DiagramChar {
+    pub fn ch(self) -> char {
+        match self {
+            DiagramChar::Symbol(ch) | DiagramChar::Digit(ch) => ch,
+            DiagramChar::Nothing => '.',
+        }
+    }
+}
+
+/// A run of digits, located by its leftmost digit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Number {
+    pub value: u32,
+    pub pos: IVec2,
+    pub len: usize,
+}
+
+impl Number {
+    /// Every cell touching the number, diagonals included. Cells off the
+    /// edge of the diagram are not filtered out.
+    pub fn neighbours(&self) -> impl Iterator<Item = IVec2> {
+        let start = self.pos;
+        let end = self.pos + IVec2::new(self.len as i32 - 1, 0);
+        let west = (-1..=1).map(move |dy| IVec2::new(start.x - 1, start.y + dy));
+        let east = (-1..=1).map(move |dy| IVec2::new(end.x + 1, end.y + dy));
+        let north = (start.x..=end.x).map(move |x| IVec2::new(x, start.y - 1));
+        let south = (start.x..=end.x).map(move |x| IVec2::new(x, start.y + 1));
+        north.chain(south).chain(east).chain(west)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub ch: char,
+    pub pos: IVec2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token {
+    Number(Number),
+    Symbol(Symbol),
+}
+
+/// The engine schematic as a grid of cells plus the numbers and symbols in
+/// it, in reading order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagram {
+    pub rows: Vec<Vec<DiagramChar>>,
+    pub tokens: Vec<Token>,
+}
+
+impl Diagram {
+    pub fn width(&self) -> usize {
+        self.rows.first().map_or(0, Vec::len)
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn get(&self, pos: IVec2) -> Option<DiagramChar> {
+        let valid_x = pos.x >= 0 && pos.x < self.width() as i32;
+        let valid_y = pos.y >= 0 && pos.y < self.height() as i32;
+        if valid_x && valid_y {
+            Some(self.rows[pos.y as usize][pos.x as usize])
+        } else {
+            None
+        }
+    }
+
+    /// The symbol at `pos`, if it belongs to `class`.
+    pub fn symbol_at(&self, pos: IVec2, class: &SymbolClass) -> Option<char> {
+        match self.get(pos)? {
+            DiagramChar::Symbol(ch) if class.contains(ch) => Some(ch),
+            _ => None,
+        }
+    }
+
+    /// Whether the number touches a symbol in `class`.
+    pub fn is_part_number(&self, number: &Number, class: &SymbolClass) -> bool {
+        number
+            .neighbours()
+            .any(|pos| self.symbol_at(pos, class).is_some())
+    }
+
+    pub fn numbers(&self) -> impl Iterator<Item = &Number> {
+        self.tokens.iter().filter_map(|t| match t {
+            Token::Number(n) => Some(n),
+            Token::Symbol(_) => None,
+        })
+    }
+
+    pub fn symbols(&self) -> impl Iterator<Item = &Symbol> {
+        self.tokens.iter().filter_map(|t| match t {
+            Token::Symbol(s) => Some(s),
+            Token::Number(_) => None,
+        })
+    }
+
+    pub fn symbols_in<'a>(&'a self, class: &'a SymbolClass) -> impl Iterator<Item = &'a Symbol> {
+        self.symbols().filter(|s| class.contains(s.ch))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn parts_by_class() {
+    let diagram = parse_diagram("467..114..\n...*......\n..35..633.\n......#...").unwrap();
+    let parts = |class: &SymbolClass| -> Vec<u32> {
+        diagram
+            .numbers()
+            .filter(|n| diagram.is_part_number(n, class))
+            .map(|n| n.value)
+            .collect()
+    };
+    assert_eq!(parts(&SymbolClass::Any), [467, 35, 633]);
+    assert_eq!(parts(&SymbolClass::one_of("#")), [633]);
+    assert_eq!(parts(&SymbolClass::one_of("@$")), []);
+    assert_eq!(
+        diagram.get(IVec2::new(3, 1)),
+        Some(DiagramChar::Symbol('*'))
+    );
+}
+
+#[test]
+fn neighbours_ring_the_number() {
+    let number = Number {
+        value: 35,
+        pos: IVec2::new(2, 2),
+        len: 2,
+    };
+    let mut ring: Vec<(i32, i32)> = number.neighbours().map(|p| (p.x, p.y)).collect();
+    ring.sort_unstable();
+    assert_eq!(
+        ring,
+        [
+            (1, 1),
+            (1, 2),
+            (1, 3),
+            (2, 1),
+            (2, 3),
+            (3, 1),
+            (3, 3),
+            (4, 1),
+            (4, 2),
+            (4, 3)
+        ]
+    );
+}
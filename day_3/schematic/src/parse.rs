@@ -0,0 +1,204 @@
+use std::fmt;
+
+use glam::IVec2;
+use nom::branch::alt;
+use nom::character::complete::{char, digit1, line_ending, none_of};
+use nom::combinator::{all_consuming, map, opt};
+use nom::multi::{many1, separated_list1};
+use nom::sequence::{pair, terminated};
+use nom::IResult;
+use nom_locate::{position, LocatedSpan};
+
+use crate::{Diagram, DiagramChar, Number, Symbol, Token};
+
+type Span<'a> = LocatedSpan<&'a str>;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// Input the grammar could not read, such as a blank line.
+    Unexpected { line: u32, column: usize },
+    /// A row whose width differs from the first row's.
+    Ragged {
+        line: u32,
+        expected: usize,
+        found: usize,
+    },
+    NumberTooLarge {
+        line: u32,
+        column: usize,
+        digits: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Unexpected { line, column } => {
+                write!(f, "line {line}, column {column}: unexpected input")
+            }
+            ParseError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line}: expected {expected} cells like the first row, found {found}"
+            ),
+            ParseError::NumberTooLarge {
+                line,
+                column,
+                digits,
+            } => write!(f, "line {line}, column {column}: {digits} is too large"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug)]
+enum Cell<'a> {
+    Digits(Span<'a>),
+    Dot,
+    Symbol(Span<'a>, char),
+}
+
+fn cell(s: Span) -> IResult<Span, Cell> {
+    alt((
+        map(digit1, Cell::Digits),
+        map(char('.'), |_| Cell::Dot),
+        map(pair(position, none_of("\r\n")), |(pos, c)| {
+            Cell::Symbol(pos, c)
+        }),
+    ))(s)
+}
+
+fn rows(s: Span) -> IResult<Span, Vec<Vec<Cell>>> {
+    all_consuming(terminated(
+        separated_list1(line_ending, many1(cell)),
+        opt(line_ending),
+    ))(s)
+}
+
+fn xy(span: &Span) -> IVec2 {
+    IVec2::new(
+        span.get_utf8_column() as i32 - 1,
+        span.location_line() as i32 - 1,
+    )
+}
+
+/// Reads the schematic in one pass, building the grid and the token list
+/// together.
+pub fn parse_diagram(input: &str) -> Result<Diagram, ParseError> {
+    let (_, cells) = rows(input.into()).map_err(|e| match e {
+        nom::Err::Error(e) | nom::Err::Failure(e) => ParseError::Unexpected {
+            line: e.input.location_line(),
+            column: e.input.get_utf8_column(),
+        },
+        nom::Err::Incomplete(_) => unreachable!("complete parsers"),
+    })?;
+
+    let mut diagram = Diagram {
+        rows: Vec::with_capacity(cells.len()),
+        tokens: Vec::new(),
+    };
+    for (y, row_cells) in cells.into_iter().enumerate() {
+        let mut row = Vec::new();
+        for cell in row_cells {
+            match cell {
+                Cell::Digits(digits) => {
+                    let too_large = || ParseError::NumberTooLarge {
+                        line: digits.location_line(),
+                        column: digits.get_utf8_column(),
+                        digits: digits.fragment().to_string(),
+                    };
+                    let value = digits.fragment().parse().map_err(|_| too_large())?;
+                    diagram.tokens.push(Token::Number(Number {
+                        value,
+                        pos: xy(&digits),
+                        len: digits.len(),
+                    }));
+                    row.extend(digits.fragment().chars().map(DiagramChar::Digit));
+                }
+                Cell::Dot => row.push(DiagramChar::Nothing),
+                Cell::Symbol(pos, ch) => {
+                    diagram
+                        .tokens
+                        .push(Token::Symbol(Symbol { ch, pos: xy(&pos) }));
+                    row.push(DiagramChar::Symbol(ch));
+                }
+            }
+        }
+        if let Some(first) = diagram.rows.first() {
+            if row.len() != first.len() {
+                return Err(ParseError::Ragged {
+                    line: y as u32 + 1,
+                    expected: first.len(),
+                    found: row.len(),
+                });
+            }
+        }
+        diagram.rows.push(row);
+    }
+    Ok(diagram)
+}
+
+#[cfg(test)]
+#[test]
+fn tokens_are_located() {
+    let diagram = parse_diagram("..35..633.\n617*....#.\n").unwrap();
+    let numbers: Vec<(u32, i32, i32, usize)> = diagram
+        .numbers()
+        .map(|n| (n.value, n.pos.x, n.pos.y, n.len))
+        .collect();
+    assert_eq!(numbers, [(35, 2, 0, 2), (633, 6, 0, 3), (617, 0, 1, 3)]);
+    let symbols: Vec<(char, i32, i32)> = diagram
+        .symbols()
+        .map(|s| (s.ch, s.pos.x, s.pos.y))
+        .collect();
+    assert_eq!(symbols, [('*', 3, 1), ('#', 8, 1)]);
+    assert_eq!(
+        diagram.get(IVec2::new(3, 1)),
+        Some(DiagramChar::Symbol('*'))
+    );
+    assert_eq!(diagram.get(IVec2::new(4, 0)), Some(DiagramChar::Nothing));
+    assert_eq!(diagram.get(IVec2::new(10, 0)), None);
+    assert_eq!((diagram.width(), diagram.height()), (10, 2));
+}
+
+#[test]
+fn crlf_and_wide_symbols() {
+    let diagram = parse_diagram("é12\r\n..€\r\n").unwrap();
+    assert_eq!(diagram.width(), 3);
+    let number = diagram.numbers().next().unwrap();
+    assert_eq!(number.pos, IVec2::new(1, 0));
+    let last = diagram.symbols().last().unwrap();
+    assert_eq!((last.ch, last.pos), ('€', IVec2::new(2, 1)));
+}
+
+#[test]
+fn errors_are_located() {
+    assert_eq!(
+        parse_diagram("..1\n\n..2"),
+        Err(ParseError::Unexpected { line: 2, column: 1 })
+    );
+    assert_eq!(
+        parse_diagram("...\n..\n"),
+        Err(ParseError::Ragged {
+            line: 2,
+            expected: 3,
+            found: 2
+        })
+    );
+    assert_eq!(
+        parse_diagram(".99999999999."),
+        Err(ParseError::NumberTooLarge {
+            line: 1,
+            column: 2,
+            digits: "99999999999".to_string()
+        })
+    );
+    assert_eq!(
+        parse_diagram(""),
+        Err(ParseError::Unexpected { line: 1, column: 1 })
+    );
+}
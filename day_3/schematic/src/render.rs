@@ -0,0 +1,180 @@
+use crate::{Adjacency, Diagram, DiagramChar, Neighbours};
+
+/// How a cell is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// A digit of a number touching a symbol.
+    Part,
+    /// A digit of a number touching no symbol.
+    Loose,
+    Gear,
+    Symbol,
+    Blank,
+}
+
+impl Style {
+    fn ansi(self) -> &'static str {
+        match self {
+            Style::Part => "\x1b[32m",
+            Style::Loose => "\x1b[31m",
+            Style::Gear => "\x1b[1;33m",
+            Style::Symbol => "\x1b[36m",
+            Style::Blank => "\x1b[2m",
+        }
+    }
+
+    fn colour(self) -> &'static str {
+        match self {
+            Style::Part => "#00cc00",
+            Style::Loose => "#ff4040",
+            Style::Gear => "#ffff66",
+            Style::Symbol => "#00cccc",
+            Style::Blank => "#333340",
+        }
+    }
+}
+
+/// The style of every cell. Numbers are parts unless `parts` lists them as
+/// unattached; symbols in `gears` with the right number of neighbours are
+/// gears.
+pub fn cell_styles(
+    diagram: &Diagram,
+    parts: &Adjacency,
+    gears: &Adjacency,
+    neighbours: Neighbours,
+) -> Vec<Vec<Style>> {
+    let mut styles: Vec<Vec<Style>> = diagram
+        .rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|c| match c {
+                    DiagramChar::Symbol(_) => Style::Symbol,
+                    DiagramChar::Digit(_) => Style::Part,
+                    DiagramChar::Nothing => Style::Blank,
+                })
+                .collect()
+        })
+        .collect();
+    for number in &parts.unattached {
+        let row = &mut styles[number.pos.y as usize];
+        let start = number.pos.x as usize;
+        row[start..start + number.len].fill(Style::Loose);
+    }
+    for gear in gears.matching(neighbours) {
+        styles[gear.symbol.pos.y as usize][gear.symbol.pos.x as usize] = Style::Gear;
+    }
+    styles
+}
+
+/// Runs of cells sharing a style, per row, as `(start column, style, text)`.
+fn runs(diagram: &Diagram, styles: &[Vec<Style>]) -> Vec<Vec<(usize, Style, String)>> {
+    diagram
+        .rows
+        .iter()
+        .zip(styles)
+        .map(|(row, row_styles)| {
+            let mut runs: Vec<(usize, Style, String)> = Vec::new();
+            for (x, (c, &style)) in row.iter().zip(row_styles).enumerate() {
+                match runs.last_mut() {
+                    Some((_, last, text)) if *last == style => text.push(c.ch()),
+                    _ => runs.push((x, style, c.ch().to_string())),
+                }
+            }
+            runs
+        })
+        .collect()
+}
+
+/// The schematic with ANSI colours, resetting at the end of each row.
+pub fn render_ansi(diagram: &Diagram, styles: &[Vec<Style>]) -> String {
+    let mut out = String::new();
+    for row in runs(diagram, styles) {
+        for (_, style, text) in row {
+            out += style.ansi();
+            out += &text;
+        }
+        out += "\x1b[0m\n";
+    }
+    out
+}
+
+const CELL_WIDTH: usize = 10;
+const CELL_HEIGHT: usize = 18;
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// A standalone SVG with one monospace text run per styled run of cells.
+pub fn render_svg(diagram: &Diagram, styles: &[Vec<Style>]) -> String {
+    let width = diagram.width() * CELL_WIDTH;
+    let height = diagram.height() * CELL_HEIGHT;
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         font-family=\"monospace\" font-size=\"16\">\n\
+         <rect width=\"{width}\" height=\"{height}\" fill=\"#0f0f23\"/>\n"
+    );
+    for (y, row) in runs(diagram, styles).into_iter().enumerate() {
+        for (x, style, text) in row {
+            out += &format!(
+                "<text x=\"{}\" y=\"{}\" textLength=\"{}\" lengthAdjust=\"spacing\" fill=\"{}\">{}</text>\n",
+                x * CELL_WIDTH,
+                (y + 1) * CELL_HEIGHT - 4,
+                text.chars().count() * CELL_WIDTH,
+                style.colour(),
+                escape(&text),
+            );
+        }
+    }
+    out += "</svg>\n";
+    out
+}
+
+#[cfg(test)]
+fn example() -> (Diagram, Vec<Vec<Style>>) {
+    use crate::SymbolClass;
+
+    let diagram = crate::parse_diagram("467..114..\n...*......\n..35..6&3.\n617$......").unwrap();
+    let parts = Adjacency::new(&diagram, &SymbolClass::Any);
+    let gears = Adjacency::new(&diagram, &SymbolClass::one_of("*"));
+    let styles = cell_styles(&diagram, &parts, &gears, Neighbours::Exactly(2));
+    (diagram, styles)
+}
+
+#[test]
+fn styles_follow_adjacency() {
+    use Style::*;
+
+    let (_, styles) = example();
+    assert_eq!(
+        styles[0],
+        [Part, Part, Part, Blank, Blank, Loose, Loose, Loose, Blank, Blank]
+    );
+    assert_eq!(styles[1][3], Gear);
+    assert_eq!(styles[3][3], Symbol);
+    assert_eq!(styles[2][6..9], [Part, Symbol, Part]);
+}
+
+#[test]
+fn ansi_colours_runs() {
+    let (diagram, styles) = example();
+    let ansi = render_ansi(&diagram, &styles);
+    let first = ansi.lines().next().unwrap();
+    assert_eq!(first, "\x1b[32m467\x1b[2m..\x1b[31m114\x1b[2m..\x1b[0m");
+    assert_eq!(ansi.lines().count(), 4);
+}
+
+#[test]
+fn svg_escapes_symbols() {
+    let (diagram, styles) = example();
+    let svg = render_svg(&diagram, &styles);
+    assert!(
+        svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\" height=\"72\"")
+    );
+    assert!(svg.contains(">&amp;</text>"));
+    assert!(svg.contains("fill=\"#ffff66\">*</text>"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+}
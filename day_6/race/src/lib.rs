@@ -0,0 +1,87 @@
+use std::ops::Range;
+
+use num_bigint::BigUint;
+
+mod model;
+mod parse;
+mod report;
+
+pub use model::RaceModel;
+pub use parse::{parse_races, Kerning, ParseError};
+pub use report::{render_csv, render_table, summarise, RaceSummary};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Race {
+    pub time: BigUint,
+    pub distance: BigUint,
+}
+
+impl Race {
+    pub fn winning_range(&self) -> Option<Range<BigUint>> {
+        winning_range(&self.time, &self.distance)
+    }
+}
+
+fn beats(time: &BigUint, hold: &BigUint, distance_to_beat: &BigUint) -> bool {
+    hold * (time - hold) > *distance_to_beat
+}
+
+/// Hold times that beat `distance_to_beat` when the boat gains 1 mm/ms per
+/// millisecond held and keeps that speed for the rest of the race.
+pub fn winning_range(time: &BigUint, distance_to_beat: &BigUint) -> Option<Range<BigUint>> {
+    let time_sq = time * time;
+    let four_dist = distance_to_beat * 4u32;
+    if time_sq < four_dist {
+        return None;
+    }
+    let half = time / 2u32;
+    // The integer root undershoots the real one by less than one, so the
+    // first winning hold is at most a couple of steps above this estimate.
+    let mut start = (time - (time_sq - four_dist).sqrt()) / 2u32;
+    if start > BigUint::ZERO {
+        start -= 1u32;
+    }
+    while start <= half && !beats(time, &start, distance_to_beat) {
+        start += 1u32;
+    }
+    if start > half {
+        return None;
+    }
+    let end = time - &start + 1u32;
+    Some(start..end)
+}
+
+#[cfg(test)]
+fn big(n: u64) -> BigUint {
+    BigUint::from(n)
+}
+
+#[test]
+fn roots_for_one_race() {
+    let r = winning_range(&big(7), &big(9));
+    assert_eq!(r, Some(big(2)..big(6)));
+}
+
+#[test]
+fn matches_brute_force() {
+    for time in 0..120u64 {
+        for dist in 0..time * time / 4 + 2 {
+            let wins: Vec<u64> = (0..=time).filter(|h| h * (time - h) > dist).collect();
+            let expected = wins.first().map(|&s| big(s)..big(wins[wins.len() - 1] + 1));
+            assert_eq!(
+                winning_range(&big(time), &big(dist)),
+                expected,
+                "time {time} dist {dist}"
+            );
+        }
+    }
+}
+
+#[test]
+fn exact_above_f64_precision() {
+    let time = big((1u64 << 32) + 7);
+    let hold = big((1u64 << 31) - 5);
+    let dist = &hold * (&time - &hold) - 1u32;
+    let r = winning_range(&time, &dist).unwrap();
+    assert_eq!(r, hold.clone()..&time - &hold + 1u32);
+}
@@ -0,0 +1,169 @@
+use std::ops::Range;
+
+use num_bigint::BigUint;
+
+use crate::winning_range;
+
+/// How a boat turns button-hold time into distance, and what counts as a win.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaceModel {
+    /// Speed gained, in mm/ms, for each millisecond the button is held.
+    pub charge_rate: BigUint,
+    /// Speed the boat cannot exceed however long the button is held.
+    pub max_speed: Option<BigUint>,
+    /// Distance a run has to cover beyond the record to count.
+    pub margin: BigUint,
+    /// Whether landing exactly on record + margin is a win.
+    pub inclusive: bool,
+}
+
+impl Default for RaceModel {
+    fn default() -> Self {
+        Self {
+            charge_rate: BigUint::from(1u32),
+            max_speed: None,
+            margin: BigUint::ZERO,
+            inclusive: false,
+        }
+    }
+}
+
+impl RaceModel {
+    pub fn speed(&self, hold: &BigUint) -> BigUint {
+        let speed = hold * &self.charge_rate;
+        match &self.max_speed {
+            Some(max) if speed > *max => max.clone(),
+            _ => speed,
+        }
+    }
+
+    pub fn distance(&self, time: &BigUint, hold: &BigUint) -> BigUint {
+        if hold > time {
+            return BigUint::ZERO;
+        }
+        self.speed(hold) * (time - hold)
+    }
+
+    /// Earliest hold time that travels the furthest.
+    pub fn best_hold(&self, time: &BigUint) -> BigUint {
+        partition_point(BigUint::ZERO, time.clone(), |hold| {
+            self.distance(time, hold) < self.distance(time, &(hold + 1u32))
+        })
+    }
+
+    /// Smallest distance that wins against `record`.
+    pub fn required(&self, record: &BigUint) -> BigUint {
+        let required = record + &self.margin;
+        if self.inclusive {
+            required
+        } else {
+            required + 1u32
+        }
+    }
+
+    pub fn winning_range(&self, time: &BigUint, record: &BigUint) -> Option<Range<BigUint>> {
+        let required = self.required(record);
+        match self.max_speed {
+            None => self.analytic_range(time, &required),
+            Some(_) => self.search_range(time, &required),
+        }
+    }
+
+    fn analytic_range(&self, time: &BigUint, required: &BigUint) -> Option<Range<BigUint>> {
+        if *required == BigUint::ZERO {
+            return Some(BigUint::ZERO..time + 1u32);
+        }
+        if self.charge_rate == BigUint::ZERO {
+            return None;
+        }
+        // rate * h * (time - h) >= required exactly when
+        // h * (time - h) > ceil(required / rate) - 1.
+        let rate = &self.charge_rate;
+        let scaled = (required + rate - 1u32) / rate;
+        winning_range(time, &(scaled - 1u32))
+    }
+
+    /// The distance is the minimum of two concave functions of the hold
+    /// time, so it is concave too: locate the peak, then bisect each side.
+    fn search_range(&self, time: &BigUint, required: &BigUint) -> Option<Range<BigUint>> {
+        let wins = |hold: &BigUint| self.distance(time, hold) >= *required;
+        let peak = self.best_hold(time);
+        if !wins(&peak) {
+            return None;
+        }
+        let start = partition_point(BigUint::ZERO, peak.clone(), |hold| !wins(hold));
+        let end = partition_point(peak + 1u32, time + 1u32, wins);
+        Some(start..end)
+    }
+}
+
+/// First value in `lo..hi` for which `pred` is false, assuming `pred` holds
+/// on a prefix of the range; `hi` if it holds throughout.
+fn partition_point(mut lo: BigUint, mut hi: BigUint, pred: impl Fn(&BigUint) -> bool) -> BigUint {
+    while lo < hi {
+        let mid = (&lo + &hi) / 2u32;
+        if pred(&mid) {
+            lo = mid + 1u32;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+#[cfg(test)]
+fn brute_force(model: &RaceModel, time: u64, record: u64) -> Option<Range<BigUint>> {
+    let required = model.required(&record.into());
+    let wins: Vec<u64> = (0..=time)
+        .filter(|&hold| model.distance(&time.into(), &hold.into()) >= required)
+        .collect();
+    let (first, last) = (wins.first()?, wins.last()?);
+    Some(BigUint::from(*first)..BigUint::from(last + 1))
+}
+
+#[test]
+fn default_model_is_the_puzzle() {
+    let model = RaceModel::default();
+    let r = model.winning_range(&BigUint::from(30u32), &BigUint::from(200u32));
+    assert_eq!(r, Some(BigUint::from(11u32)..BigUint::from(20u32)));
+}
+
+#[test]
+fn best_hold_is_earliest_peak() {
+    let model = RaceModel::default();
+    assert_eq!(model.best_hold(&7u32.into()), BigUint::from(3u32));
+    assert_eq!(model.best_hold(&30u32.into()), BigUint::from(15u32));
+    let capped = RaceModel {
+        max_speed: Some(4u32.into()),
+        ..RaceModel::default()
+    };
+    assert_eq!(capped.best_hold(&30u32.into()), BigUint::from(4u32));
+}
+
+#[test]
+fn models_match_brute_force() {
+    let caps = [None, Some(0u32), Some(1), Some(4), Some(9)];
+    for rate in 0..4u32 {
+        for cap in caps {
+            for margin in [0u32, 3] {
+                for inclusive in [false, true] {
+                    let model = RaceModel {
+                        charge_rate: rate.into(),
+                        max_speed: cap.map(BigUint::from),
+                        margin: margin.into(),
+                        inclusive,
+                    };
+                    for time in 0..16u64 {
+                        for record in 0..time * time / 2 + 2 {
+                            assert_eq!(
+                                model.winning_range(&time.into(), &record.into()),
+                                brute_force(&model, time, record),
+                                "{model:?} time {time} record {record}"
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
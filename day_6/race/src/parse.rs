@@ -0,0 +1,143 @@
+use std::fmt;
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, line_ending, multispace0, space0, space1},
+    combinator::all_consuming,
+    multi::separated_list1,
+    sequence::{delimited, preceded, separated_pair, terminated},
+    IResult,
+};
+use num_bigint::BigUint;
+
+use crate::Race;
+
+/// How the digit groups on each line are read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kerning {
+    /// Every group of digits is its own race.
+    Spaced,
+    /// The spaces are bad kerning: all groups form a single number.
+    Kerned,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Malformed(String),
+    CountMismatch { times: usize, distances: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Malformed(e) => write!(f, "malformed race sheet: {e}"),
+            ParseError::CountMismatch { times, distances } => {
+                write!(f, "{times} times but {distances} distances")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub fn parse_races(input: &str, kerning: Kerning) -> Result<Vec<Race>, ParseError> {
+    let (_, (times, distances)) = parse_sheet(input)
+        .map_err(|e| ParseError::Malformed(e.map_input(|s| s.to_owned()).to_string()))?;
+    let (times, distances) = match kerning {
+        Kerning::Spaced => (to_numbers(&times), to_numbers(&distances)),
+        Kerning::Kerned => (vec![concat(&times)], vec![concat(&distances)]),
+    };
+    if times.len() != distances.len() {
+        return Err(ParseError::CountMismatch {
+            times: times.len(),
+            distances: distances.len(),
+        });
+    }
+    Ok(times
+        .into_iter()
+        .zip(distances)
+        .map(|(time, distance)| Race { time, distance })
+        .collect())
+}
+
+fn to_numbers(groups: &[&str]) -> Vec<BigUint> {
+    groups.iter().map(|g| concat(&[g])).collect()
+}
+
+fn concat(groups: &[&str]) -> BigUint {
+    BigUint::parse_bytes(groups.concat().as_bytes(), 10).expect("groups are all digits")
+}
+
+fn parse_line<'a>(label: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<&'a str>> {
+    preceded(
+        terminated(tag(label), space0),
+        terminated(separated_list1(space1, digit1), space0),
+    )
+}
+
+fn parse_sheet(input: &str) -> IResult<&str, (Vec<&str>, Vec<&str>)> {
+    all_consuming(delimited(
+        multispace0,
+        separated_pair(parse_line("Time:"), line_ending, parse_line("Distance:")),
+        multispace0,
+    ))(input)
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "Time:      7  15   30
+Distance:  9  40  200";
+
+#[test]
+fn spaced_example() {
+    let races = parse_races(EXAMPLE, Kerning::Spaced).unwrap();
+    let pairs: Vec<(BigUint, BigUint)> = [(7u32, 9u32), (15, 40), (30, 200)]
+        .into_iter()
+        .map(|(t, d)| (t.into(), d.into()))
+        .collect();
+    let parsed: Vec<(BigUint, BigUint)> = races.into_iter().map(|r| (r.time, r.distance)).collect();
+    assert_eq!(parsed, pairs);
+}
+
+#[test]
+fn kerned_example() {
+    let races = parse_races(EXAMPLE, Kerning::Kerned).unwrap();
+    assert_eq!(
+        races,
+        vec![Race {
+            time: 71530u32.into(),
+            distance: 940200u32.into()
+        }]
+    );
+}
+
+#[test]
+fn rejects_mismatched_counts() {
+    let e = "Time:      7  15   30\nDistance:  9  40";
+    assert_eq!(
+        parse_races(e, Kerning::Spaced),
+        Err(ParseError::CountMismatch {
+            times: 3,
+            distances: 2
+        })
+    );
+    assert!(parse_races(e, Kerning::Kerned).is_ok());
+}
+
+#[test]
+fn rejects_malformed_sheets() {
+    for e in [
+        "",
+        "Time: 7\n",
+        "Distance: 9\nTime: 7",
+        "Time: 7 x\nDistance: 9",
+        "Time: 7\nDistance: 9\nTime: 8",
+    ] {
+        assert!(
+            matches!(
+                parse_races(e, Kerning::Spaced),
+                Err(ParseError::Malformed(_))
+            ),
+            "{e:?}"
+        );
+    }
+}
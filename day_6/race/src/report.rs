@@ -0,0 +1,220 @@
+use std::ops::Range;
+
+use num_bigint::BigUint;
+
+use crate::{Race, RaceModel};
+
+/// What a single race looks like under a given model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaceSummary {
+    pub time: BigUint,
+    pub record: BigUint,
+    /// Smallest distance that wins under the model.
+    pub required: BigUint,
+    pub winning: Option<Range<BigUint>>,
+    pub best_hold: BigUint,
+    pub best_distance: BigUint,
+}
+
+impl RaceSummary {
+    pub fn new(race: &Race, model: &RaceModel) -> Self {
+        let best_hold = model.best_hold(&race.time);
+        Self {
+            time: race.time.clone(),
+            record: race.distance.clone(),
+            required: model.required(&race.distance),
+            winning: model.winning_range(&race.time, &race.distance),
+            best_distance: model.distance(&race.time, &best_hold),
+            best_hold,
+        }
+    }
+
+    pub fn ways_to_win(&self) -> BigUint {
+        self.winning
+            .as_ref()
+            .map_or(BigUint::ZERO, |r| &r.end - &r.start)
+    }
+
+    /// How far the best run goes past the record, or `None` if it falls short.
+    pub fn margin(&self) -> Option<BigUint> {
+        (self.best_distance >= self.record).then(|| &self.best_distance - &self.record)
+    }
+
+    /// How far the best run goes past the smallest winning distance, or
+    /// `None` if nothing wins.
+    pub fn over_required(&self) -> Option<BigUint> {
+        self.winning
+            .as_ref()
+            .map(|_| &self.best_distance - &self.required)
+    }
+
+    fn cells(&self) -> [String; 9] {
+        let (first, last) = match &self.winning {
+            Some(r) => (r.start.to_string(), (&r.end - 1u32).to_string()),
+            None => ("-".to_string(), "-".to_string()),
+        };
+        [
+            self.time.to_string(),
+            self.record.to_string(),
+            first,
+            last,
+            self.ways_to_win().to_string(),
+            self.best_hold.to_string(),
+            self.best_distance.to_string(),
+            self.margin().map_or("-".to_string(), |m| m.to_string()),
+            self.over_required()
+                .map_or("-".to_string(), |m| m.to_string()),
+        ]
+    }
+}
+
+const HEADERS: [&str; 9] = [
+    "time",
+    "record",
+    "first_win",
+    "last_win",
+    "ways",
+    "best_hold",
+    "best_distance",
+    "margin",
+    "over_required",
+];
+
+pub fn summarise(races: &[Race], model: &RaceModel) -> Vec<RaceSummary> {
+    races.iter().map(|r| RaceSummary::new(r, model)).collect()
+}
+
+/// Text table, one row per race, with the winning holds as an inclusive
+/// range. Cells are ASCII numbers or ranges, so widths are byte lengths and
+/// every column is right-aligned.
+pub fn render_table(summaries: &[RaceSummary]) -> String {
+    const COLUMNS: [&str; 8] = [
+        "time",
+        "record",
+        "wins",
+        "ways",
+        "best_hold",
+        "best_distance",
+        "margin",
+        "over_required",
+    ];
+    let rows: Vec<[String; 8]> = summaries
+        .iter()
+        .map(|s| {
+            let [time, record, first, last, ways, hold, distance, margin, over] = s.cells();
+            let wins = match s.winning {
+                Some(_) => format!("{first}..={last}"),
+                None => first,
+            };
+            [time, record, wins, ways, hold, distance, margin, over]
+        })
+        .collect();
+    let mut widths = COLUMNS.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let mut out = String::new();
+    for row in std::iter::once(COLUMNS.map(String::from)).chain(rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, w)| format!("{cell:>w$}"))
+            .collect();
+        out += &cells.join("  ");
+        out += "\n";
+    }
+    out
+}
+
+pub fn render_csv(summaries: &[RaceSummary]) -> String {
+    let mut out = HEADERS.join(",") + "\n";
+    for summary in summaries {
+        out += &summary.cells().join(",");
+        out += "\n";
+    }
+    out
+}
+
+#[cfg(test)]
+fn example() -> Vec<RaceSummary> {
+    let races = crate::parse_races(
+        "Time:      7  15   30\nDistance:  9  40  200",
+        crate::Kerning::Spaced,
+    )
+    .unwrap();
+    summarise(&races, &RaceModel::default())
+}
+
+#[test]
+fn example_table() {
+    let expected = "\
+time  record     wins  ways  best_hold  best_distance  margin  over_required
+   7       9    2..=5     4          3             12       3              2
+  15      40   4..=11     8          7             56      16             15
+  30     200  11..=19     9         15            225      25             24
+";
+    assert_eq!(render_table(&example()), expected);
+}
+
+#[test]
+fn csv_splits_the_winning_range() {
+    let csv = render_csv(&example());
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("time,record,first_win,last_win,ways,best_hold,best_distance,margin,over_required")
+    );
+    assert_eq!(lines.next(), Some("7,9,2,5,4,3,12,3,2"));
+    assert_eq!(lines.count(), 2);
+}
+
+#[test]
+fn unwinnable_race_shows_dashes() {
+    let race = Race {
+        time: 4u32.into(),
+        distance: 10u32.into(),
+    };
+    let summary = RaceSummary::new(&race, &RaceModel::default());
+    assert_eq!(summary.ways_to_win(), BigUint::ZERO);
+    assert_eq!(summary.margin(), None);
+    assert_eq!(
+        render_csv(&[summary]).lines().nth(1),
+        Some("4,10,-,-,0,2,4,-,-")
+    );
+}
+
+#[test]
+fn tying_the_record_is_not_a_win() {
+    let race = Race {
+        time: 4u32.into(),
+        distance: 4u32.into(),
+    };
+    let summary = RaceSummary::new(&race, &RaceModel::default());
+    assert_eq!(
+        (summary.margin(), summary.over_required()),
+        (Some(BigUint::ZERO), None)
+    );
+    let inclusive = RaceModel {
+        inclusive: true,
+        ..RaceModel::default()
+    };
+    assert_eq!(
+        RaceSummary::new(&race, &inclusive).over_required(),
+        Some(BigUint::ZERO)
+    );
+    let strict = RaceModel {
+        margin: 2u32.into(),
+        ..RaceModel::default()
+    };
+    let race = Race {
+        time: 7u32.into(),
+        distance: 9u32.into(),
+    };
+    let summary = RaceSummary::new(&race, &strict);
+    assert_eq!(
+        (summary.margin(), summary.over_required()),
+        (Some(3u32.into()), Some(BigUint::ZERO))
+    );
+}
@@ -0,0 +1,118 @@
+use crate::{hand::wild_substitute, HandTuple, HandType, RuleSet};
+
+/// How one hand came to its rank and winnings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub rank: usize,
+    pub labels: String,
+    pub hand_type: HandType,
+    /// Label each wild card was counted as, if the hand has any to count.
+    pub wild_as: Option<char>,
+    pub n_wild: usize,
+    pub bid: u32,
+    pub winnings: u32,
+    /// Another hand has the same type, so card order decided between them.
+    pub tie_broken: bool,
+}
+
+/// Explains hands that are already sorted weakest first.
+pub fn explain(ranked: &[HandTuple], rules: &RuleSet) -> Vec<Explanation> {
+    ranked
+        .iter()
+        .enumerate()
+        .map(|(i, hand_tuple)| {
+            let hand = &hand_tuple.hand;
+            let same_type = |other: Option<&HandTuple>| {
+                other.is_some_and(|o| o.hand.hand_type == hand.hand_type)
+            };
+            let n_wild = hand.cards.iter().filter(|&&c| rules.is_wild(c)).count();
+            Explanation {
+                rank: i + 1,
+                labels: hand.cards.iter().map(|&c| rules.label(c)).collect(),
+                hand_type: hand.hand_type.clone(),
+                wild_as: (n_wild > 0)
+                    .then(|| wild_substitute(&hand.cards, rules))
+                    .flatten()
+                    .map(|c| rules.label(c)),
+                n_wild,
+                bid: hand_tuple.bid,
+                winnings: (i as u32 + 1) * hand_tuple.bid,
+                tie_broken: same_type(i.checked_sub(1).and_then(|j| ranked.get(j)))
+                    || same_type(ranked.get(i + 1)),
+            }
+        })
+        .collect()
+}
+
+fn wild_cell(e: &Explanation) -> String {
+    match (e.n_wild, e.wild_as) {
+        (0, _) => String::new(),
+        (n, Some(label)) => format!("{n} → {label}"),
+        (n, None) => format!("{n} → any"),
+    }
+}
+
+/// The wider of a column's header and its longest cell, in characters.
+fn width(header: &str, cells: impl IntoIterator<Item = usize>) -> usize {
+    cells.into_iter().fold(header.len(), usize::max)
+}
+
+/// Aligned text table of explanations, one hand per line. Numbers are
+/// right-aligned and text left-aligned.
+pub fn render_explanation(explanations: &[Explanation]) -> String {
+    let wilds: Vec<String> = explanations.iter().map(wild_cell).collect();
+    let types: Vec<String> = explanations
+        .iter()
+        .map(|e| e.hand_type.to_string())
+        .collect();
+    let digits = |n: u32| n.to_string().len();
+    let rank_w = width("rank", [explanations.len().to_string().len()]);
+    let hand_w = width(
+        "hand",
+        explanations.iter().map(|e| e.labels.chars().count()),
+    );
+    let type_w = width("type", types.iter().map(|t| t.chars().count()));
+    let wild_w = width("wild", wilds.iter().map(|w| w.chars().count()));
+    let bid_w = width("bid", explanations.iter().map(|e| digits(e.bid)));
+    let winnings_w = width("winnings", explanations.iter().map(|e| digits(e.winnings)));
+
+    let mut out = format!(
+        "{:>rank_w$}  {:<hand_w$}  {:<type_w$}  {:<wild_w$}  {:>bid_w$}  {:>winnings_w$}  tie\n",
+        "rank", "hand", "type", "wild", "bid", "winnings"
+    );
+    for ((e, hand_type), wild) in explanations.iter().zip(&types).zip(&wilds) {
+        let line = format!(
+            "{:>rank_w$}  {:<hand_w$}  {hand_type:<type_w$}  {wild:<wild_w$}  {:>bid_w$}  {:>winnings_w$}  {}",
+            e.rank,
+            e.labels,
+            e.bid,
+            e.winnings,
+            if e.tie_broken { "tie" } else { "" },
+        );
+        out += line.trim_end();
+        out += "\n";
+    }
+    out
+}
+
+#[test]
+fn example_explanation() {
+    let rules = RuleSet::jokers();
+    let hands = crate::parse_hand_tuples(
+        "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483",
+        &rules,
+    )
+    .unwrap();
+    let expected = "\
+rank  hand   type            wild   bid  winnings  tie
+   1  32T3K  one pair               765       765
+   2  KK677  two pair                28        56
+   3  T55J5  four of a kind  1 → 5  684      2052  tie
+   4  QQQJA  four of a kind  1 → Q  483      1932  tie
+   5  KTJJT  four of a kind  2 → T  220      1100  tie
+";
+    assert_eq!(
+        render_explanation(&explain(&crate::rank(hands, &rules), &rules)),
+        expected
+    );
+}
@@ -0,0 +1,257 @@
+use std::fmt;
+
+use crate::rules::{Card, RuleSet, TieBreak};
+
+/// The shape of a hand: sizes of its groups of two or more matching cards,
+/// largest first. Comparing these lexicographically gives the puzzle's
+/// ordering (five of a kind > four of a kind > full house > ...) and extends
+/// it to hands of any size.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandType {
+    groups: Vec<usize>,
+}
+
+impl HandType {
+    pub fn new(groups: &[usize]) -> HandType {
+        let mut groups: Vec<usize> = groups.iter().copied().filter(|&g| g >= 2).collect();
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+        HandType { groups }
+    }
+
+    pub fn groups(&self) -> &[usize] {
+        &self.groups
+    }
+}
+
+fn number_word(n: usize) -> String {
+    const WORDS: [&str; 11] = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    ];
+    WORDS
+        .get(n)
+        .map_or_else(|| n.to_string(), |w| w.to_string())
+}
+
+impl fmt::Display for HandType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.groups.as_slice() {
+            [] => return write!(f, "high card"),
+            [3, 2] => return write!(f, "full house"),
+            _ => {}
+        }
+        let mut parts = Vec::new();
+        for group in self.groups.chunk_by(|a, b| a == b) {
+            let (size, count) = (group[0], group.len());
+            parts.push(match (size, count) {
+                (2, _) => format!("{} pair", number_word(count)),
+                (_, 1) => format!("{} of a kind", number_word(size)),
+                _ => format!("{} of a kind ×{count}", number_word(size)),
+            });
+        }
+        write!(f, "{}", parts.join(" and "))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Hand {
+    pub hand_type: HandType,
+    /// The cards in the order the rule set's tie-break compares them.
+    tie_break: Vec<Card>,
+    pub cards: Vec<Card>,
+}
+
+impl Hand {
+    pub fn new(cards: Vec<Card>, rules: &RuleSet) -> Hand {
+        let mut tie_break = cards.clone();
+        if rules.tie_break == TieBreak::HighestFirst {
+            tie_break.sort_unstable_by(|a, b| b.cmp(a));
+        }
+        Hand {
+            hand_type: strongest_hand_type(&cards, rules),
+            tie_break,
+            cards,
+        }
+    }
+}
+
+/// Groups of matching natural cards as `(size, card)`, largest first and
+/// strongest first among equal sizes.
+fn natural_groups(cards: &[Card], rules: &RuleSet) -> Vec<(usize, Card)> {
+    let mut natural: Vec<Card> = cards
+        .iter()
+        .copied()
+        .filter(|&c| !rules.is_wild(c))
+        .collect();
+    natural.sort_unstable();
+    let mut groups: Vec<(usize, Card)> = natural
+        .chunk_by(|a, b| a == b)
+        .map(|group| (group.len(), group[0]))
+        .collect();
+    groups.sort_unstable_by(|a, b| b.cmp(a));
+    groups
+}
+
+/// The card every wild card in the hand stands in for, or `None` if there
+/// is no natural card for them to join.
+pub fn wild_substitute(cards: &[Card], rules: &RuleSet) -> Option<Card> {
+    natural_groups(cards, rules).first().map(|&(_, card)| card)
+}
+
+/// Sizes of the groups of matching cards, largest first, once every wild
+/// card has joined the largest group of natural cards.
+pub fn count_signature(cards: &[Card], rules: &RuleSet) -> Vec<usize> {
+    let mut signature: Vec<usize> = natural_groups(cards, rules)
+        .into_iter()
+        .map(|(size, _)| size)
+        .collect();
+    let n_wild = cards.iter().filter(|&&c| rules.is_wild(c)).count();
+    match signature.first_mut() {
+        Some(largest) => *largest += n_wild,
+        None if n_wild > 0 => signature.push(n_wild),
+        None => {}
+    }
+    signature
+}
+
+pub fn strongest_hand_type(cards: &[Card], rules: &RuleSet) -> HandType {
+    HandType::new(&count_signature(cards, rules))
+}
+
+#[cfg(test)]
+fn every_hand(deck: u8, size: usize) -> impl Iterator<Item = Vec<Card>> {
+    (0..(deck as usize).pow(size as u32)).map(move |mut n| {
+        (0..size)
+            .map(|_| {
+                let card = Card((n % deck as usize) as u8);
+                n /= deck as usize;
+                card
+            })
+            .collect()
+    })
+}
+
+/// Classifies a five-card hand without wild cards from its number of
+/// distinct cards and its largest group.
+#[cfg(test)]
+fn reference_type(cards: &[Card]) -> HandType {
+    let largest = cards
+        .iter()
+        .map(|c| cards.iter().filter(|o| *o == c).count())
+        .max()
+        .unwrap();
+    let mut distinct = cards.to_vec();
+    distinct.sort_unstable();
+    distinct.dedup();
+    let groups: &[usize] = match (largest, distinct.len()) {
+        (5, 1) => &[5],
+        (4, 2) => &[4],
+        (3, 2) => &[3, 2],
+        (3, 3) => &[3],
+        (2, 3) => &[2, 2],
+        (2, 4) => &[2],
+        (1, 5) => &[],
+        other => panic!("impossible hand shape {other:?}"),
+    };
+    HandType::new(groups)
+}
+
+#[test]
+fn puzzle_hand_types_are_ordered() {
+    let ordered: Vec<HandType> = [&[][..], &[2], &[2, 2], &[3], &[3, 2], &[4], &[5]]
+        .into_iter()
+        .map(HandType::new)
+        .collect();
+    assert!(ordered.windows(2).all(|w| w[0] < w[1]));
+    let names: Vec<String> = ordered.iter().map(|t| t.to_string()).collect();
+    assert_eq!(
+        names,
+        [
+            "high card",
+            "one pair",
+            "two pair",
+            "three of a kind",
+            "full house",
+            "four of a kind",
+            "five of a kind"
+        ]
+    );
+}
+
+#[test]
+fn every_hand_without_wilds() {
+    let rules = RuleSet::standard();
+    for cards in every_hand(13, 5) {
+        assert_eq!(
+            strongest_hand_type(&cards, &rules),
+            reference_type(&cards),
+            "{cards:?}"
+        );
+    }
+}
+
+#[test]
+fn every_hand_with_jokers() {
+    let rules = RuleSet::jokers();
+    let joker = rules.card('J').unwrap();
+    for cards in every_hand(13, 5) {
+        // Try every way of replacing each joker independently. A card not
+        // already in the hand is as good as any other such card, so one
+        // stands in for all of them.
+        let mut options: Vec<Card> = cards.iter().copied().filter(|&c| c != joker).collect();
+        options.push((1..13).map(Card).find(|c| !cards.contains(c)).unwrap());
+        options.sort_unstable();
+        options.dedup();
+        let jokers: Vec<usize> = (0..cards.len()).filter(|&i| cards[i] == joker).collect();
+        let best = (0..options.len().pow(jokers.len() as u32))
+            .map(|mut n| {
+                let mut replaced = cards.clone();
+                for &i in &jokers {
+                    replaced[i] = options[n % options.len()];
+                    n /= options.len();
+                }
+                reference_type(&replaced)
+            })
+            .max()
+            .unwrap();
+        assert_eq!(strongest_hand_type(&cards, &rules), best, "{cards:?}");
+    }
+}
+
+#[test]
+fn all_wild_hand() {
+    let rules = RuleSet::jokers();
+    let jokers = vec![rules.card('J').unwrap(); 5];
+    assert_eq!(count_signature(&jokers, &rules), vec![5]);
+    assert_eq!(strongest_hand_type(&jokers, &rules), HandType::new(&[5]));
+}
+
+#[test]
+fn jokers_join_the_largest_then_strongest_group() {
+    let rules = RuleSet::jokers();
+    let cards =
+        |labels: &str| -> Vec<Card> { labels.chars().map(|c| rules.card(c).unwrap()).collect() };
+    let label = |cards: &[Card]| wild_substitute(cards, &rules).map(|c| rules.label(c));
+    assert_eq!(label(&cards("T55J5")), Some('5'));
+    assert_eq!(label(&cards("KTJJT")), Some('T'));
+    assert_eq!(label(&cards("KQJ23")), Some('K'));
+    assert_eq!(label(&cards("JJJJJ")), None);
+}
+
+#[test]
+fn six_card_hand_types() {
+    let rules = RuleSet::jokers().with_hand_size(6);
+    let hand_type = |labels: &str| {
+        let cards: Vec<Card> = labels.chars().map(|c| rules.card(c).unwrap()).collect();
+        strongest_hand_type(&cards, &rules)
+    };
+    assert_eq!(hand_type("KKKKKJ").to_string(), "six of a kind");
+    assert_eq!(hand_type("KKQQ22").to_string(), "three pair");
+    assert_eq!(hand_type("KKKQQQ").to_string(), "three of a kind ×2");
+    assert_eq!(
+        hand_type("KKKQQJ").to_string(),
+        "four of a kind and one pair"
+    );
+    assert!(hand_type("KKKQQJ") > hand_type("KKKQQQ"));
+    assert!(hand_type("KKKQQQ") > hand_type("KKKQQ2"));
+    assert!(hand_type("KKQQ22") > hand_type("KKQQ23"));
+}
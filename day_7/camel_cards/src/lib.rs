@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+mod explain;
+mod hand;
+mod odds;
+mod parse;
+mod rules;
+
+pub use explain::{explain, render_explanation, Explanation};
+pub use hand::{count_signature, strongest_hand_type, wild_substitute, Hand, HandType};
+pub use odds::{exact_odds, render_odds, sampled_odds, Odds};
+pub use parse::{parse_hand, parse_hand_tuples, ParseError};
+pub use rules::{Card, DuplicatePolicy, RuleSet, RuleSetError, TieBreak};
+
+#[derive(Debug)]
+pub struct HandTuple {
+    pub hand: Hand,
+    pub bid: u32,
+    /// Line of the input the hand was read from, counting from 1.
+    pub line: usize,
+}
+
+/// Pairs of input lines dealing the same cards, as `(first, repeat)`.
+pub fn find_duplicates(hand_tuples: &[HandTuple]) -> Vec<(usize, usize)> {
+    let mut first_seen = HashMap::new();
+    hand_tuples
+        .iter()
+        .filter_map(|t| {
+            let first = *first_seen.entry(&t.hand.cards).or_insert(t.line);
+            (first != t.line).then_some((first, t.line))
+        })
+        .collect()
+}
+
+/// Sorts hands weakest first, so each one's rank is its position plus one.
+/// Identical hands keep their input order unless the rule set ranks them by
+/// bid, so the result never depends on the sort algorithm.
+pub fn rank(mut hand_tuples: Vec<HandTuple>, rules: &RuleSet) -> Vec<HandTuple> {
+    match rules.duplicates {
+        DuplicatePolicy::Reject | DuplicatePolicy::InputOrder => {
+            hand_tuples.sort_by(|a, b| a.hand.cmp(&b.hand))
+        }
+        DuplicatePolicy::BidOrder => {
+            hand_tuples.sort_by(|a, b| a.hand.cmp(&b.hand).then(a.bid.cmp(&b.bid)))
+        }
+    }
+    hand_tuples
+}
+
+pub fn total_winnings(input: &str, rules: &RuleSet) -> Result<u32, ParseError> {
+    let hand_tuples = rank(parse_hand_tuples(input, rules)?, rules);
+    Ok(hand_tuples
+        .iter()
+        .enumerate()
+        .map(|(i, hand_tuple)| (i as u32 + 1) * hand_tuple.bid)
+        .sum())
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+#[cfg(test)]
+fn cards(rules: &RuleSet, labels: &str) -> Vec<Card> {
+    labels.chars().map(|c| rules.card(c).unwrap()).collect()
+}
+
+#[test]
+fn example() {
+    assert_eq!(total_winnings(EXAMPLE, &RuleSet::standard()), Ok(6440));
+    assert_eq!(total_winnings(EXAMPLE, &RuleSet::jokers()), Ok(5905));
+}
+
+#[test]
+fn one_joker() {
+    let rules = RuleSet::jokers();
+    let c = parse_hand("KTA4J", &rules);
+    assert_eq!(c, Ok(Hand::new(cards(&rules, "KTA4J"), &rules)));
+    assert_eq!(c.unwrap().hand_type, HandType::new(&[2]));
+}
+
+#[test]
+fn custom_wild_card() {
+    let rules = RuleSet::new("23456789TJQKA", "A", TieBreak::InOrder).unwrap();
+    let hand = Hand::new(cards(&rules, "AKKAQ"), &rules);
+    assert_eq!(hand.hand_type, HandType::new(&[4]));
+    // Unlike jokers, wild aces keep their place at the top for tie-breaks.
+    assert!(hand > Hand::new(cards(&rules, "KKKAQ"), &rules));
+}
+
+#[test]
+fn highest_first_tie_break() {
+    let rules = RuleSet::new("23456789TJQKA", "", TieBreak::HighestFirst).unwrap();
+    let a = Hand::new(cards(&rules, "2A345"), &rules);
+    let b = Hand::new(cards(&rules, "K2345"), &rules);
+    assert!(a > b);
+    let in_order = RuleSet::standard();
+    assert!(
+        Hand::new(cards(&in_order, "2A345"), &in_order)
+            < Hand::new(cards(&in_order, "K2345"), &in_order)
+    );
+}
+
+#[test]
+fn duplicate_policies() {
+    let input = "KK677 28\n32T3K 10\nKK677 5\n32T3K 765";
+    assert_eq!(
+        total_winnings(input, &RuleSet::standard()),
+        Err(ParseError::DuplicateHand {
+            first_line: 1,
+            line: 3
+        })
+    );
+    let in_order = RuleSet::standard().with_duplicates(DuplicatePolicy::InputOrder);
+    assert_eq!(
+        total_winnings(input, &in_order),
+        Ok(10 + 765 * 2 + 28 * 3 + 5 * 4)
+    );
+    let by_bid = RuleSet::standard().with_duplicates(DuplicatePolicy::BidOrder);
+    assert_eq!(
+        total_winnings(input, &by_bid),
+        Ok(10 + 765 * 2 + 5 * 3 + 28 * 4)
+    );
+    let hands = parse_hand_tuples(input, &in_order).unwrap();
+    assert_eq!(find_duplicates(&hands), [(1, 3), (2, 4)]);
+}
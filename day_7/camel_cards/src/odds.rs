@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{strongest_hand_type, Card, HandType, RuleSet};
+
+/// How often each hand type comes up among a set of hands.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Odds {
+    pub counts: BTreeMap<HandType, u64>,
+    pub total: u64,
+}
+
+impl Odds {
+    fn add(&mut self, cards: &[Card], rules: &RuleSet) {
+        *self
+            .counts
+            .entry(strongest_hand_type(cards, rules))
+            .or_default() += 1;
+        self.total += 1;
+    }
+
+    pub fn probability(&self, hand_type: &HandType) -> f64 {
+        self.counts.get(hand_type).copied().unwrap_or(0) as f64 / self.total as f64
+    }
+}
+
+/// Classifies every possible hand, one card from the deck per position.
+pub fn exact_odds(rules: &RuleSet) -> Odds {
+    let deck = rules.deck_size();
+    let mut odds = Odds::default();
+    let mut cards = vec![Card(0); rules.hand_size];
+    loop {
+        odds.add(&cards, rules);
+        // Count through hands like digits of a base-`deck` number.
+        let Some(i) = cards.iter().position(|c| (c.0 as usize) + 1 < deck) else {
+            return odds;
+        };
+        cards[i].0 += 1;
+        cards[..i].fill(Card(0));
+    }
+}
+
+/// Classifies `n` hands dealt uniformly at random from a seeded generator.
+pub fn sampled_odds(rules: &RuleSet, n: u64, seed: u64) -> Odds {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut odds = Odds::default();
+    let mut cards = vec![Card(0); rules.hand_size];
+    for _ in 0..n {
+        for card in cards.iter_mut() {
+            *card = Card(rng.gen_range(0..rules.deck_size()) as u8);
+        }
+        odds.add(&cards, rules);
+    }
+    odds
+}
+
+/// One row per hand type, weakest first, with the chance of being dealt
+/// that type and the chance of being dealt that type or anything weaker.
+pub fn render_odds(odds: &Odds) -> String {
+    let rows: Vec<(String, u64, f64, f64)> = odds
+        .counts
+        .iter()
+        .scan(0, |so_far, (hand_type, &count)| {
+            *so_far += count;
+            Some((
+                hand_type.to_string(),
+                count,
+                count as f64 / odds.total as f64,
+                *so_far as f64 / odds.total as f64,
+            ))
+        })
+        .collect();
+    let type_width = rows
+        .iter()
+        .map(|r| r.0.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let count_width = odds.total.to_string().len().max(5);
+    let mut out = format!(
+        "{:<type_width$}  {:>count_width$}  {:>11}  {:>11}\n",
+        "type", "count", "probability", "cumulative"
+    );
+    for (name, count, probability, cumulative) in rows {
+        out += &format!(
+            "{name:<type_width$}  {count:>count_width$}  {probability:>11.6}  {cumulative:>11.6}\n"
+        );
+    }
+    out
+}
+
+#[test]
+fn exact_odds_without_wilds() {
+    let odds = exact_odds(&RuleSet::standard());
+    let counts: Vec<(Vec<usize>, u64)> = odds
+        .counts
+        .iter()
+        .map(|(t, &n)| (t.groups().to_vec(), n))
+        .collect();
+    assert_eq!(
+        counts,
+        [
+            (vec![], 13 * 12 * 11 * 10 * 9),
+            (vec![2], 13 * 220 * 60),
+            (vec![2, 2], 78 * 11 * 30),
+            (vec![3], 13 * 66 * 20),
+            (vec![3, 2], 13 * 12 * 10),
+            (vec![4], 13 * 12 * 5),
+            (vec![5], 13),
+        ]
+    );
+    assert_eq!(odds.total, 13u64.pow(5));
+}
+
+#[test]
+fn exact_odds_with_jokers() {
+    let odds = exact_odds(&RuleSet::jokers());
+    assert_eq!(odds.total, 13u64.pow(5));
+    // All natural cards alike, with any number of jokers, plus all jokers.
+    assert_eq!(odds.counts[&HandType::new(&[5])], 12 * 31 + 1);
+    // Any joker rules out high card.
+    assert_eq!(odds.counts[&HandType::new(&[])], 12 * 11 * 10 * 9 * 8);
+}
+
+#[test]
+fn sampling_approaches_exact_odds() {
+    let rules = RuleSet::jokers();
+    let exact = exact_odds(&rules);
+    let sampled = sampled_odds(&rules, 20_000, 7);
+    assert_eq!(sampled, sampled_odds(&rules, 20_000, 7));
+    for hand_type in exact.counts.keys() {
+        let error = (exact.probability(hand_type) - sampled.probability(hand_type)).abs();
+        assert!(error < 0.02, "{hand_type}: off by {error}");
+    }
+}
+
+#[test]
+fn odds_table() {
+    let rules = RuleSet::new("AB", "", crate::TieBreak::InOrder)
+        .unwrap()
+        .with_hand_size(2);
+    let expected = "\
+type       count  probability   cumulative
+high card      2     0.500000     0.500000
+one pair       2     0.500000     1.000000
+";
+    assert_eq!(render_odds(&exact_odds(&rules)), expected);
+}
@@ -0,0 +1,154 @@
+use std::fmt;
+
+use nom::{
+    character::complete::{anychar, digit1, multispace0, newline, space1},
+    combinator::{all_consuming, map_opt, map_res},
+    multi::{many1, separated_list1},
+    sequence::{separated_pair, terminated},
+    IResult,
+};
+
+use crate::{
+    find_duplicates,
+    hand::Hand,
+    rules::{Card, DuplicatePolicy, RuleSet},
+    HandTuple,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Malformed(String),
+    HandSize {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    DuplicateHand {
+        first_line: usize,
+        line: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Malformed(e) => write!(f, "malformed hands: {e}"),
+            ParseError::HandSize {
+                line,
+                expected,
+                found,
+            } => write!(f, "line {line}: expected {expected} cards, found {found}"),
+            ParseError::DuplicateHand { first_line, line } => {
+                write!(f, "line {line}: same hand as line {first_line}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn parse_card<'a>(input: &'a str, rules: &RuleSet) -> IResult<&'a str, Card> {
+    map_opt(anychar, |c| rules.card(c))(input)
+}
+
+fn parse_cards<'a>(input: &'a str, rules: &RuleSet) -> IResult<&'a str, Vec<Card>> {
+    many1(|i| parse_card(i, rules))(input)
+}
+
+fn to_hand(cards: Vec<Card>, line: usize, rules: &RuleSet) -> Result<Hand, ParseError> {
+    if cards.len() != rules.hand_size {
+        return Err(ParseError::HandSize {
+            line,
+            expected: rules.hand_size,
+            found: cards.len(),
+        });
+    }
+    Ok(Hand::new(cards, rules))
+}
+
+fn malformed(e: nom::Err<nom::error::Error<&str>>) -> ParseError {
+    ParseError::Malformed(e.map_input(|s| s.to_owned()).to_string())
+}
+
+/// Parses a single hand, such as `KTJJT`.
+pub fn parse_hand(input: &str, rules: &RuleSet) -> Result<Hand, ParseError> {
+    let (_, cards) = all_consuming(|i| parse_cards(i, rules))(input).map_err(malformed)?;
+    to_hand(cards, 1, rules)
+}
+
+/// Parses one `hand bid` pair per line, rejecting repeated hands unless the
+/// rule set says how to rank them.
+pub fn parse_hand_tuples(input: &str, rules: &RuleSet) -> Result<Vec<HandTuple>, ParseError> {
+    let parse_u32 = map_res(digit1, |s: &str| s.parse::<u32>());
+    let parse_line = separated_pair(|i| parse_cards(i, rules), space1, parse_u32);
+    let (_, lines) = all_consuming(terminated(
+        separated_list1(newline, parse_line),
+        multispace0,
+    ))(input)
+    .map_err(malformed)?;
+    let hand_tuples = lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, (cards, bid))| {
+            Ok(HandTuple {
+                hand: to_hand(cards, i + 1, rules)?,
+                bid,
+                line: i + 1,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if rules.duplicates == DuplicatePolicy::Reject {
+        if let Some(&(first_line, line)) = find_duplicates(&hand_tuples).first() {
+            return Err(ParseError::DuplicateHand { first_line, line });
+        }
+    }
+    Ok(hand_tuples)
+}
+
+#[test]
+fn rejects_wrong_hand_sizes() {
+    let rules = RuleSet::standard();
+    assert_eq!(
+        parse_hand_tuples("32T3K 765\nT55J5J 684", &rules).unwrap_err(),
+        ParseError::HandSize {
+            line: 2,
+            expected: 5,
+            found: 6
+        }
+    );
+    assert_eq!(
+        parse_hand("KTJT", &rules).unwrap_err(),
+        ParseError::HandSize {
+            line: 1,
+            expected: 5,
+            found: 4
+        }
+    );
+    assert!(parse_hand("KTJTQA", &rules.with_hand_size(6)).is_ok());
+}
+
+#[test]
+fn rejects_unknown_cards_and_trailing_input() {
+    let rules = RuleSet::standard();
+    for e in ["32X3K 765", "32T3K", "32T3K 765\nrubbish", "32T3K 765 12"] {
+        assert!(
+            matches!(parse_hand_tuples(e, &rules), Err(ParseError::Malformed(_))),
+            "{e:?}"
+        );
+    }
+    assert!(parse_hand_tuples("32T3K 765\n", &rules).is_ok());
+}
+
+#[test]
+fn rejects_duplicate_hands_by_default() {
+    let input = "32T3K 765\nKK677 28\n32T3K 10";
+    assert_eq!(
+        parse_hand_tuples(input, &RuleSet::standard()).unwrap_err(),
+        ParseError::DuplicateHand {
+            first_line: 1,
+            line: 3
+        }
+    );
+    let rules = RuleSet::standard().with_duplicates(DuplicatePolicy::InputOrder);
+    assert_eq!(parse_hand_tuples(input, &rules).unwrap().len(), 3);
+}
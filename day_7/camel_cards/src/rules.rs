@@ -0,0 +1,148 @@
+use std::fmt;
+
+/// A card, identified by its strength under the rule set that parsed it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Card(pub u8);
+
+/// How two hands of the same type are ordered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Compare the cards position by position as dealt.
+    InOrder,
+    /// Compare each hand's strongest card first, then the next strongest.
+    HighestFirst,
+}
+
+/// What to do when the same hand is dealt more than once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Reject the input, as the puzzle promises every hand is unique.
+    Reject,
+    /// Rank identical hands in the order they appear in the input.
+    InputOrder,
+    /// Rank identical hands by bid, smallest bid lowest.
+    BidOrder,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RuleSetError {
+    NoCards,
+    DuplicateCard(char),
+    UnknownWild(char),
+    TooManyCards(usize),
+}
+
+impl fmt::Display for RuleSetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleSetError::NoCards => write!(f, "a deck needs at least one card"),
+            RuleSetError::DuplicateCard(c) => write!(f, "card {c:?} ranked twice"),
+            RuleSetError::UnknownWild(c) => write!(f, "wild card {c:?} is not in the deck"),
+            RuleSetError::TooManyCards(n) => write!(f, "{n} cards is more than a deck can rank"),
+        }
+    }
+}
+
+impl std::error::Error for RuleSetError {}
+
+/// Which cards exist, how they rank, which are wild, how ties break, how
+/// many cards make a hand and how repeated hands are handled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RuleSet {
+    order: Vec<char>,
+    wild: Vec<bool>,
+    pub tie_break: TieBreak,
+    pub hand_size: usize,
+    pub duplicates: DuplicatePolicy,
+}
+
+impl RuleSet {
+    /// `order` lists the card labels from weakest to strongest; every label
+    /// in `wild` can stand in for any other card when classifying a hand.
+    pub fn new(order: &str, wild: &str, tie_break: TieBreak) -> Result<RuleSet, RuleSetError> {
+        let order: Vec<char> = order.chars().collect();
+        if order.is_empty() {
+            return Err(RuleSetError::NoCards);
+        }
+        if order.len() > u8::MAX as usize + 1 {
+            return Err(RuleSetError::TooManyCards(order.len()));
+        }
+        for (i, c) in order.iter().enumerate() {
+            if order[..i].contains(c) {
+                return Err(RuleSetError::DuplicateCard(*c));
+            }
+        }
+        if let Some(c) = wild.chars().find(|c| !order.contains(c)) {
+            return Err(RuleSetError::UnknownWild(c));
+        }
+        let wild = order.iter().map(|c| wild.contains(*c)).collect();
+        Ok(RuleSet {
+            order,
+            wild,
+            tie_break,
+            hand_size: 5,
+            duplicates: DuplicatePolicy::Reject,
+        })
+    }
+
+    pub fn with_hand_size(self, hand_size: usize) -> RuleSet {
+        RuleSet { hand_size, ..self }
+    }
+
+    pub fn with_duplicates(self, duplicates: DuplicatePolicy) -> RuleSet {
+        RuleSet { duplicates, ..self }
+    }
+
+    /// Camel Cards as first explained: `J` is a Jack.
+    pub fn standard() -> RuleSet {
+        RuleSet::new("23456789TJQKA", "", TieBreak::InOrder).expect("valid rules")
+    }
+
+    /// Camel Cards with `J` as a Joker: wild, and weakest on its own.
+    pub fn jokers() -> RuleSet {
+        RuleSet::new("J23456789TQKA", "J", TieBreak::InOrder).expect("valid rules")
+    }
+
+    pub fn deck_size(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn card(&self, label: char) -> Option<Card> {
+        let rank = self.order.iter().position(|&c| c == label)?;
+        Some(Card(rank as u8))
+    }
+
+    pub fn label(&self, card: Card) -> char {
+        self.order[card.0 as usize]
+    }
+
+    pub fn is_wild(&self, card: Card) -> bool {
+        self.wild[card.0 as usize]
+    }
+}
+
+#[test]
+fn rejects_bad_rule_sets() {
+    assert_eq!(
+        RuleSet::new("23452", "", TieBreak::InOrder),
+        Err(RuleSetError::DuplicateCard('2'))
+    );
+    assert_eq!(
+        RuleSet::new("2345", "J", TieBreak::InOrder),
+        Err(RuleSetError::UnknownWild('J'))
+    );
+    assert_eq!(
+        RuleSet::new("", "", TieBreak::InOrder),
+        Err(RuleSetError::NoCards)
+    );
+}
+
+#[test]
+fn joker_is_weakest_and_wild() {
+    let rules = RuleSet::jokers();
+    let joker = rules.card('J').unwrap();
+    assert!(rules.is_wild(joker));
+    assert!(joker < rules.card('2').unwrap());
+    assert_eq!(rules.label(joker), 'J');
+    assert!(!RuleSet::standard().is_wild(RuleSet::standard().card('J').unwrap()));
+}